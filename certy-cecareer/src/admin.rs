@@ -0,0 +1,168 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    pub fn owner_get(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+    pub fn owner_pending(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+    //First step of the ownership handover, the new owner has to accept it
+    #[payable]
+    pub fn owner_transfer_propose(&mut self, new_owner_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.pending_owner_id = Some(new_owner_id.clone());
+        let owner_transfer_propose_log: EventLog = EventLog {
+            standard: CECAREER_STANDARD_NAME.to_string(),
+            version: CECAREER_VERSION.to_string(),
+            event: EventLogVariant::OwnerTransferPropose(vec![OwnerTransferLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                old_owner_id: self.owner_id.to_string(),
+                new_owner_id: new_owner_id.to_string(),
+            }]),
+        };
+        owner_transfer_propose_log.emit();
+    }
+    #[payable]
+    pub fn owner_transfer_accept(&mut self) {
+        assert_one_yocto();
+        let new_owner_id = self
+            .pending_owner_id
+            .take()
+            .expect("No pending owner transfer");
+        assert_eq!(
+            env::predecessor_account_id(),
+            new_owner_id,
+            "Not pending owner"
+        );
+        let old_owner_id = std::mem::replace(&mut self.owner_id, new_owner_id);
+        let owner_transfer_accept_log: EventLog = EventLog {
+            standard: CECAREER_STANDARD_NAME.to_string(),
+            version: CECAREER_VERSION.to_string(),
+            event: EventLogVariant::OwnerTransferAccept(vec![OwnerTransferLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                old_owner_id: old_owner_id.to_string(),
+                new_owner_id: self.owner_id.to_string(),
+            }]),
+        };
+        owner_transfer_accept_log.emit();
+    }
+    #[payable]
+    pub fn admin_add(&mut self, account_id: AccountId) {
+        assert_at_least_one_yocto();
+        self.assert_owner();
+        let initial_storage_usage = env::storage_usage();
+        assert!(self.admins.insert(&account_id), "Already an admin");
+        let admin_add_log: EventLog = EventLog {
+            standard: CECAREER_STANDARD_NAME.to_string(),
+            version: CECAREER_VERSION.to_string(),
+            event: EventLogVariant::AdminAdd(vec![AdminLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_ids: vec![account_id.to_string()],
+            }]),
+        };
+        admin_add_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    #[payable]
+    pub fn admin_remove(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        let initial_storage_usage = env::storage_usage();
+        assert!(self.admins.remove(&account_id), "Not an admin");
+        let admin_remove_log: EventLog = EventLog {
+            standard: CECAREER_STANDARD_NAME.to_string(),
+            version: CECAREER_VERSION.to_string(),
+            event: EventLogVariant::AdminRemove(vec![AdminLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_ids: vec![account_id.to_string()],
+            }]),
+        };
+        admin_remove_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    pub fn admins(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+        paginate(self.admins.as_vector(), from_index, limit).items
+    }
+    pub fn is_admin(&self, account_id: AccountId) -> bool {
+        account_id == self.owner_id || self.admins.contains(&account_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn context(predecessor: usize, deposit: Balance) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(predecessor))
+            .attached_deposit(deposit);
+        builder
+    }
+
+    //contract of accounts(0) with job "job" created by accounts(1)
+    fn setup() -> Contract {
+        testing_env!(context(1, 10u128.pow(24)).build());
+        let mut contract = Contract::new(accounts(0));
+        contract.job_create(
+            "job".to_string(),
+            JobMetadata {
+                issued_at: None,
+                updated_at: None,
+                extra: None,
+                reference: Some("bafkrei".to_string()),
+                reference_hash: None,
+            },
+        );
+        contract
+    }
+
+    #[test]
+    fn admin_add_and_remove() {
+        let mut contract = setup();
+        testing_env!(context(0, 10u128.pow(24)).build());
+        contract.admin_add(accounts(3));
+        assert!(contract.is_admin(accounts(3)));
+        assert_eq!(contract.admins(None, None), vec![accounts(3)]);
+
+        testing_env!(context(0, 1).build());
+        contract.admin_remove(accounts(3));
+        assert!(!contract.is_admin(accounts(3)));
+        assert!(contract.admins(None, None).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Not contract owner")]
+    fn admin_add_by_admin_panics() {
+        let mut contract = setup();
+        testing_env!(context(0, 10u128.pow(24)).build());
+        contract.admin_add(accounts(3));
+        testing_env!(context(3, 10u128.pow(24)).build());
+        contract.admin_add(accounts(4));
+    }
+
+    #[test]
+    fn admin_deletes_job_of_another_owner() {
+        let mut contract = setup();
+        testing_env!(context(0, 10u128.pow(24)).build());
+        contract.admin_add(accounts(3));
+        testing_env!(context(3, 1).build());
+        contract.job_delete("job".to_string());
+        assert!(contract.job_info("job".to_string()).is_none());
+        assert!(contract.jobs_for_owner(accounts(1), None, None).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Not admin")]
+    fn job_delete_by_other_account_panics() {
+        let mut contract = setup();
+        testing_env!(context(2, 1).build());
+        contract.job_delete("job".to_string());
+    }
+}
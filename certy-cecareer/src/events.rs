@@ -1,5 +1,6 @@
 use near_sdk::{
     env,
+    json_types::U128,
     serde::{Deserialize, Serialize},
     serde_json,
 };
@@ -16,6 +17,12 @@ pub enum EventLogVariant {
     JobCreate(Vec<JobCreateLog>),
     JobUpdate(Vec<JobUpdateLog>),
     JobDelete(Vec<JobDeleteLog>),
+    OwnerTransferPropose(Vec<OwnerTransferLog>),
+    OwnerTransferAccept(Vec<OwnerTransferLog>),
+    AdminAdd(Vec<AdminLog>),
+    AdminRemove(Vec<AdminLog>),
+    SponsorDeposit(Vec<SponsorLog>),
+    SponsorWithdraw(Vec<SponsorLog>),
 }
 
 /// Interface to capture data about an event
@@ -95,3 +102,43 @@ pub struct JobDeleteLog {
     pub authorized_id: Option<String>,
     pub job_ids: Vec<JobId>,
 }
+
+/// An event log to capture contract owner transfer propose or accept
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "new_owner.near"
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnerTransferLog {
+    pub authorized_id: Option<String>,
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+}
+
+/// An event log to capture admin add or remove
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `account_ids`: ["admin.near"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdminLog {
+    pub authorized_id: Option<String>,
+    pub account_ids: Vec<String>,
+}
+
+/// An event log to capture sponsorship deposit or withdraw
+///
+/// Arguments
+/// * `sponsor_id`: the account paying the storage
+/// * `beneficiary_id`: the account whose storage is paid
+/// * `amount`: yoctoNEAR added to or taken from the sponsorship
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorLog {
+    pub sponsor_id: String,
+    pub beneficiary_id: String,
+    pub amount: U128,
+}
@@ -27,30 +27,23 @@ pub(crate) fn assert_at_least_one_yocto() {
     )
 }
 
-//refund the initial deposit based on the amount of storage that was used up
-pub(crate) fn refund_deposit(storage_used: u64) {
-    //get how much it would cost to store the information
-    let required_cost = env::storage_byte_cost() * Balance::from(storage_used);
-    //get the attached deposit
-    let attached_deposit = env::attached_deposit();
-
-    //make sure that the attached deposit is greater than or equal to the required cost
-    assert!(
-        required_cost <= attached_deposit,
-        "Must attach {} yoctoNEAR to cover storage",
-        required_cost,
-    );
-
-    //get the refund amount from the attached deposit - required cost
-    let refund = attached_deposit - required_cost;
-
-    //if the refund is greater than 1 yocto NEAR, we refund the predecessor that amount
-    if refund > 1 {
-        Promise::new(env::predecessor_account_id()).transfer(refund);
-    }
-}
-
 impl Contract {
+    //used to make sure the user is the contract owner
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Not contract owner"
+        );
+    }
+    //used to make sure the user is the contract owner or an appointed admin
+    pub(crate) fn assert_admin(&self) {
+        let account_id = env::predecessor_account_id();
+        assert!(
+            account_id == self.owner_id || self.admins.contains(&account_id),
+            "Not admin"
+        );
+    }
     //used to make sure the user is the owner of the job
     pub(crate) fn assert_job_owner(&self, owner_id: AccountId, job_id: &JobId) {
         let job = self.jobs_by_id.get(job_id).expect("No Job");
@@ -106,7 +99,7 @@ impl Contract {
         let old_job_metadata = self.job_metadata_by_id.get(job_id).unwrap();
         let mut job_metadata = metadata.clone();
         job_metadata.updated_at = Some(env::block_timestamp_ms());
-        self.job_metadata_by_id.insert(&job_id, &job_metadata);
+        self.job_metadata_by_id.insert(job_id, &job_metadata);
         let job_update_log: EventLog = EventLog {
             standard: CECAREER_STANDARD_NAME.to_string(),
             version: CECAREER_VERSION.to_string(),
@@ -128,6 +121,7 @@ impl Contract {
         );
         self.jobs_by_id.remove(&job_id);
         self.job_metadata_by_id.remove(&job_id);
+        self.storage_payer_by_job.remove(&job_id);
         let job_delete_log: EventLog = EventLog {
             standard: CECAREER_STANDARD_NAME.to_string(),
             version: CECAREER_VERSION.to_string(),
@@ -152,7 +146,7 @@ impl Contract {
             .unwrap_or_else(|| {
                 UnorderedSet::new(
                     StorageKey::JobPerOwnerInner {
-                        account_id_hash: hash_account_id(&account_id),
+                        account_id_hash: hash_account_id(account_id),
                     }
                     .try_to_vec()
                     .unwrap(),
@@ -1,18 +1,27 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LookupMap, UnorderedMap, UnorderedSet};
-use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{env, near_bindgen, AccountId, Balance, CryptoHash, PanicOnDefault, Promise};
 
+pub use certy_common::*;
 pub use crate::job::*;
 pub use crate::events::*;
 use crate::internal::*;
+#[allow(unused_imports)]
 pub use crate::manage_job::*;
+pub use crate::quote::*;
+pub use crate::upgrade::*;
 
+mod admin;
 mod job;
 mod events;
 mod internal;
 mod manage_job;
+mod quote;
+mod sponsorship;
+mod storage;
+mod upgrade;
 
 
 pub const CECAREER_STANDARD_NAME: &str = "cecareer";
@@ -21,11 +30,24 @@ pub const CECAREER_VERSION: &str = "0.1.0";
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
+    //layout of this struct, used by `migrate` when new code is deployed. It comes first so it
+    //can be read before knowing the layout
+    pub state_version: StateVersion,
     //contract owner
     pub owner_id: AccountId,
     pub jobs_per_owner: LookupMap<AccountId, UnorderedSet<JobId>>,
     pub jobs_by_id: LookupMap<JobId, Job>,
     pub job_metadata_by_id: UnorderedMap<JobId, JobMetadata>,
+    //account proposed as the new contract owner, until it accepts the handover
+    pub pending_owner_id: Option<AccountId>,
+    //accounts appointed by the contract owner to administer the contract
+    pub admins: UnorderedSet<AccountId>,
+    //keeps track of the NEP-145 storage balance available for a given account
+    pub storage_deposits: LookupMap<AccountId, Balance>,
+    //keeps track of the storage sponsorship left by sponsor for a given beneficiary
+    pub sponsorships_per_beneficiary: LookupMap<AccountId, UnorderedMap<AccountId, Balance>>,
+    //keeps track of the sponsor that paid the storage of a given job ID
+    pub storage_payer_by_job: LookupMap<JobId, AccountId>,
 }
 
 /// Helper structure for keys of the persistent collections.
@@ -35,13 +57,19 @@ pub enum StorageKey {
     JobPerOwnerInner { account_id_hash: CryptoHash },
     JobsById,
     JobMetadataById,
+    Admins,
+    StorageDeposits,
+    SponsorshipsPerBeneficiary,
+    SponsorshipsPerBeneficiaryInner { account_id_hash: CryptoHash },
+    StoragePayerByJob,
 }
 
 #[near_bindgen]
 impl Contract {
     #[init]
     pub fn new(owner_id: AccountId) -> Self {
-        let this = Self {
+        Self {
+            state_version: StateVersion::V1,
             jobs_per_owner: LookupMap::new(
                 StorageKey::JobsPerOwner.try_to_vec().unwrap(),
             ),
@@ -50,7 +78,15 @@ impl Contract {
                 StorageKey::JobMetadataById.try_to_vec().unwrap(),
             ),
             owner_id,
-        };
-        this
+            pending_owner_id: None,
+            admins: UnorderedSet::new(StorageKey::Admins.try_to_vec().unwrap()),
+            storage_deposits: LookupMap::new(StorageKey::StorageDeposits.try_to_vec().unwrap()),
+            sponsorships_per_beneficiary: LookupMap::new(
+                StorageKey::SponsorshipsPerBeneficiary.try_to_vec().unwrap(),
+            ),
+            storage_payer_by_job: LookupMap::new(
+                StorageKey::StoragePayerByJob.try_to_vec().unwrap(),
+            ),
+        }
     }
 }
@@ -6,10 +6,20 @@ impl Contract {
     pub fn job_create(&mut self, job_id: JobId, metadata: JobMetadata) {
         assert_at_least_one_yocto();
         let initial_storage_usage = env::storage_usage();
-        self.internal_job_create(job_id, env::predecessor_account_id(), metadata);
-        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
-
-        refund_deposit(required_storage_in_bytes);
+        self.internal_job_create(job_id.clone(), env::predecessor_account_id(), metadata);
+        let payer_id = self.internal_storage_payer(
+            &env::predecessor_account_id(),
+            env::storage_usage() - initial_storage_usage + STORAGE_PAYER_ENTRY_BYTES,
+        );
+        if payer_id != env::predecessor_account_id() {
+            self.storage_payer_by_job.insert(&job_id, &payer_id);
+        }
+        self.internal_storage_charge(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+            env::attached_deposit(),
+        );
     }
     #[payable]
     pub fn job_update(&mut self, job_id: JobId, metadata: JobMetadata) {
@@ -23,31 +33,31 @@ impl Contract {
 
         let initial_storage_usage = env::storage_usage();
         self.internal_job_update(&job_id, &job_metadata);
-        let mut required_storage_in_bytes = 0;
-        if env::storage_usage() < initial_storage_usage {
-            let released_storage = initial_storage_usage - env::storage_usage();
-            Promise::new(env::predecessor_account_id())
-                .transfer(Balance::from(released_storage) * env::storage_byte_cost());
-        } else {
-            required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
-        }
-        refund_deposit(required_storage_in_bytes);
+        //changes in size are settled with whoever paid for the job
+        let payer_id = self.job_storage_payer(job_id).unwrap();
+        self.internal_storage_adjust(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+        );
     }
+    //Job owner deletes a job, the contract owner or an admin can take down any job
     #[payable]
     pub fn job_delete(&mut self, job_id: JobId) {
         assert_one_yocto();
-        self.assert_job_owner(env::predecessor_account_id(), &job_id);
+        let job = self.jobs_by_id.get(&job_id).expect("No Job");
+        if env::predecessor_account_id() != job.owner_id {
+            self.assert_admin();
+        }
+        let payer_id = self.job_storage_payer(job_id.clone()).unwrap();
         let initial_storage_usage = env::storage_usage();
         self.internal_job_delete(job_id);
-        let mut required_storage_in_bytes = 0;
-        if env::storage_usage() < initial_storage_usage {
-            let released_storage = initial_storage_usage - env::storage_usage();
-            Promise::new(env::predecessor_account_id())
-                .transfer(Balance::from(released_storage) * env::storage_byte_cost());
-        } else {
-            required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
-        }
-        refund_deposit(required_storage_in_bytes);
+        //freed storage goes back to whoever paid for the job
+        self.internal_storage_release(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+        );
     }
     //Query for all the jobs of an owner
     pub fn jobs_for_owner(
@@ -56,21 +66,23 @@ impl Contract {
         from_index: Option<U128>,
         limit: Option<u64>,
     ) -> Vec<JsonJob> {
+        self.jobs_for_owner_page(account_id, from_index, limit)
+            .items
+    }
+    pub fn jobs_for_owner_page(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Page<JsonJob> {
         let jobs_for_owner_set = self.jobs_per_owner.get(&account_id);
         let jobs = if let Some(jobs_for_owner_set) = jobs_for_owner_set {
             jobs_for_owner_set
         } else {
-            return vec![];
+            return Page::empty();
         };
 
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        jobs
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(50) as usize)
-            .map(|job_id| self.job_info(job_id.clone()).unwrap())
-            .collect()
+        paginate(jobs.as_vector(), from_index, limit).map(|job_id| self.job_info(job_id).unwrap())
     }
     pub fn job_info(&self, job_id: JobId) -> Option<JsonJob> {
         if let Some(job) = self.jobs_by_id.get(&job_id) {
@@ -0,0 +1,119 @@
+use crate::*;
+
+//bytes the runtime charges for every stored record on top of its key and value
+pub const STORAGE_RECORD_OVERHEAD_BYTES: u64 = 40;
+
+//Storage estimate of a call, `deposit` is the yoctoNEAR to attach when the caller pays for it
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageQuote {
+    pub bytes: U64,
+    pub deposit: U128,
+}
+
+impl StorageQuote {
+    pub(crate) fn from_bytes(bytes: u64) -> Self {
+        StorageQuote {
+            bytes: U64(bytes),
+            deposit: U128(Balance::from(bytes) * env::storage_byte_cost()),
+        }
+    }
+}
+
+//borsh serialized length of a value, as written to storage
+pub(crate) fn borsh_len<T: BorshSerialize>(value: &T) -> u64 {
+    value.try_to_vec().unwrap().len() as u64
+}
+
+//bytes of a new `LookupMap` entry: a single record under `prefix + key`
+pub(crate) fn lookup_map_entry_bytes(prefix_len: u64, key_len: u64, value_len: u64) -> u64 {
+    STORAGE_RECORD_OVERHEAD_BYTES + prefix_len + key_len + value_len
+}
+
+//bytes of a new `UnorderedMap` entry: the key index plus an element in the keys and values vectors
+pub(crate) fn unordered_map_entry_bytes(prefix_len: u64, key_len: u64, value_len: u64) -> u64 {
+    lookup_map_entry_bytes(prefix_len + 1, key_len, 8)
+        + lookup_map_entry_bytes(prefix_len + 1, 8, key_len)
+        + lookup_map_entry_bytes(prefix_len + 1, 8, value_len)
+}
+
+//bytes of a new `UnorderedSet` element: the element index plus an element in the elements vector
+pub(crate) fn unordered_set_entry_bytes(prefix_len: u64, element_len: u64) -> u64 {
+    lookup_map_entry_bytes(prefix_len + 1, element_len, 8)
+        + lookup_map_entry_bytes(prefix_len + 1, 8, element_len)
+}
+
+#[near_bindgen]
+impl Contract {
+    //Estimate the storage `job_create` takes for the given payload
+    pub fn job_create_quote(
+        &self,
+        account_id: AccountId,
+        job_id: JobId,
+        metadata: JobMetadata,
+    ) -> StorageQuote {
+        let job = Job {
+            owner_id: account_id.clone(),
+        };
+        let mut job_metadata = metadata;
+        job_metadata.issued_at = Some(env::block_timestamp_ms());
+        job_metadata.updated_at = Some(env::block_timestamp_ms());
+        let job_id_len = borsh_len(&job_id);
+
+        let mut bytes = lookup_map_entry_bytes(
+            borsh_len(&StorageKey::JobsById),
+            job_id_len,
+            borsh_len(&job),
+        ) + unordered_map_entry_bytes(
+            borsh_len(&StorageKey::JobMetadataById),
+            job_id_len,
+            borsh_len(&job_metadata),
+        );
+        let inner_prefix = StorageKey::JobPerOwnerInner {
+            account_id_hash: hash_account_id(&account_id),
+        }
+        .try_to_vec()
+        .unwrap();
+        if !self.jobs_per_owner.contains_key(&account_id) {
+            bytes += lookup_map_entry_bytes(
+                borsh_len(&StorageKey::JobsPerOwner),
+                borsh_len(&account_id),
+                borsh_len(&UnorderedSet::<JobId>::new(inner_prefix.clone())),
+            );
+        }
+        bytes += unordered_set_entry_bytes(inner_prefix.len() as u64, job_id_len);
+        StorageQuote::from_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    #[test]
+    fn job_create_quote_covers_storage() {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(1))
+            .attached_deposit(10u128.pow(24))
+            .build());
+        let mut contract = Contract::new(accounts(0));
+        //the first job of an owner, then a second one with a longer ID
+        for job_id in ["job", "a-much-longer-job-id"].iter() {
+            let metadata = JobMetadata {
+                issued_at: None,
+                updated_at: None,
+                extra: None,
+                reference: Some("bafkrei".to_string()),
+                reference_hash: None,
+            };
+            let quote =
+                contract.job_create_quote(accounts(1), job_id.to_string(), metadata.clone());
+            let initial_storage_usage = env::storage_usage();
+            contract.job_create(job_id.to_string(), metadata);
+            assert!(quote.bytes.0 >= env::storage_usage() - initial_storage_usage);
+        }
+    }
+}
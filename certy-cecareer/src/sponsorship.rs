@@ -0,0 +1,138 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //Pre-fund the storage of the jobs created by a beneficiary
+    #[payable]
+    pub fn sponsor_deposit(&mut self, beneficiary_id: AccountId) -> U128 {
+        let (amount, available) = self.internal_sponsor_deposit(&beneficiary_id);
+        let sponsor_deposit_log: EventLog = EventLog {
+            standard: CECAREER_STANDARD_NAME.to_string(),
+            version: CECAREER_VERSION.to_string(),
+            event: EventLogVariant::SponsorDeposit(vec![SponsorLog {
+                sponsor_id: env::predecessor_account_id().to_string(),
+                beneficiary_id: beneficiary_id.to_string(),
+                amount: U128(amount),
+            }]),
+        };
+        sponsor_deposit_log.emit();
+        U128(available)
+    }
+    //Take back the unused sponsorship, all of it if no amount is given
+    #[payable]
+    pub fn sponsor_withdraw(&mut self, beneficiary_id: AccountId, amount: Option<U128>) -> U128 {
+        assert_one_yocto();
+        let (amount, available) = self.internal_sponsor_withdraw(&beneficiary_id, amount);
+        let sponsor_withdraw_log: EventLog = EventLog {
+            standard: CECAREER_STANDARD_NAME.to_string(),
+            version: CECAREER_VERSION.to_string(),
+            event: EventLogVariant::SponsorWithdraw(vec![SponsorLog {
+                sponsor_id: env::predecessor_account_id().to_string(),
+                beneficiary_id: beneficiary_id.to_string(),
+                amount: U128(amount),
+            }]),
+        };
+        sponsor_withdraw_log.emit();
+        U128(available)
+    }
+    pub fn sponsorship_balance(&self, sponsor_id: AccountId, beneficiary_id: AccountId) -> U128 {
+        U128(self.internal_sponsorship_get(&sponsor_id, &beneficiary_id))
+    }
+    //Query for all the sponsorships of a beneficiary
+    pub fn sponsorships_for_beneficiary(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonSponsorship> {
+        let sponsorships_for_beneficiary = self.sponsorships_per_beneficiary.get(&account_id);
+        let sponsorships = if let Some(sponsorships_for_beneficiary) = sponsorships_for_beneficiary
+        {
+            sponsorships_for_beneficiary
+        } else {
+            return vec![];
+        };
+
+        paginate_map(&sponsorships, from_index, limit)
+            .items
+            .into_iter()
+            .map(|(sponsor_id, available)| JsonSponsorship {
+                sponsor_id,
+                beneficiary_id: account_id.clone(),
+                available: U128(available),
+            })
+            .collect()
+    }
+    //Account that paid the storage of a job
+    pub fn job_storage_payer(&self, job_id: JobId) -> Option<AccountId> {
+        let job = self.jobs_by_id.get(&job_id)?;
+        Some(
+            self.storage_payer_by_job
+                .get(&job_id)
+                .unwrap_or(job.owner_id),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn context(predecessor: usize, deposit: Balance) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(predecessor))
+            .attached_deposit(deposit);
+        builder
+    }
+
+    fn metadata(extra: Option<String>) -> JobMetadata {
+        JobMetadata {
+            issued_at: None,
+            updated_at: None,
+            extra,
+            reference: Some("bafkrei".to_string()),
+            reference_hash: None,
+        }
+    }
+
+    //job "job" of accounts(1) paid by accounts(3), whose sponsorship is then run down to 1 yoctoNEAR
+    fn setup() -> Contract {
+        testing_env!(context(3, 10u128.pow(24)).build());
+        let mut contract = Contract::new(accounts(0));
+        contract.sponsor_deposit(accounts(1));
+        testing_env!(context(1, 1).build());
+        contract.job_create("job".to_string(), metadata(None));
+        assert_eq!(
+            contract.job_storage_payer("job".to_string()),
+            Some(accounts(3))
+        );
+        testing_env!(context(3, 1).build());
+        let available = contract.sponsorship_balance(accounts(3), accounts(1)).0;
+        contract.sponsor_withdraw(accounts(1), Some(U128(available - 1)));
+        contract
+    }
+
+    #[test]
+    fn job_update_beyond_sponsorship_is_paid_from_deposit() {
+        let mut contract = setup();
+        testing_env!(context(1, 10u128.pow(24)).build());
+        contract.job_update("job".to_string(), metadata(Some("a".repeat(100))));
+        assert_eq!(contract.sponsorship_balance(accounts(3), accounts(1)).0, 0);
+        assert_eq!(
+            contract.job_info("job".to_string()).unwrap().metadata.extra,
+            Some("a".repeat(100))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "to cover storage")]
+    fn job_update_beyond_sponsorship_without_deposit_panics() {
+        let mut contract = setup();
+        testing_env!(context(1, 1).build());
+        contract.job_update("job".to_string(), metadata(Some("a".repeat(100))));
+    }
+}
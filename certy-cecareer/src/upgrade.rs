@@ -0,0 +1,148 @@
+use crate::*;
+use near_sdk::Gas;
+
+//gas kept by `upgrade` for deploying the code, the rest is given to `migrate`
+const GAS_FOR_UPGRADE: Gas = Gas(20_000_000_000_000);
+//least gas `migrate` is given to read and rewrite the state
+const MIN_GAS_FOR_MIGRATE: Gas = Gas(10_000_000_000_000);
+
+//Layout of the contract state, stored first in `Contract`. A variant is added for every released
+//layout of `Contract`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StateVersion {
+    V1,
+}
+
+//The state layout deployed before the state was versioned
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV0 {
+    pub owner_id: AccountId,
+    pub jobs_per_owner: LookupMap<AccountId, UnorderedSet<JobId>>,
+    pub jobs_by_id: LookupMap<JobId, Job>,
+    pub job_metadata_by_id: UnorderedMap<JobId, JobMetadata>,
+}
+
+impl From<ContractV0> for Contract {
+    fn from(old: ContractV0) -> Self {
+        Self {
+            state_version: StateVersion::V1,
+            owner_id: old.owner_id,
+            jobs_per_owner: old.jobs_per_owner,
+            jobs_by_id: old.jobs_by_id,
+            job_metadata_by_id: old.job_metadata_by_id,
+            pending_owner_id: None,
+            admins: UnorderedSet::new(StorageKey::Admins.try_to_vec().unwrap()),
+            storage_deposits: LookupMap::new(StorageKey::StorageDeposits.try_to_vec().unwrap()),
+            sponsorships_per_beneficiary: LookupMap::new(
+                StorageKey::SponsorshipsPerBeneficiary.try_to_vec().unwrap(),
+            ),
+            storage_payer_by_job: LookupMap::new(
+                StorageKey::StoragePayerByJob.try_to_vec().unwrap(),
+            ),
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    //Called by `upgrade` right after the new code is deployed, moves the stored state to the current layout
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let state = env::storage_read(b"STATE").expect("Contract is not initialized");
+        //the versioned layout starts with its marker, followed by the u32 length of `owner_id`
+        //whose low byte is never 0. The baseline layout starts with that length, so its second byte is 0
+        if state.len() > 1 && state[1] != 0 {
+            match StateVersion::try_from_slice(&state[..1]).expect("Unknown state layout") {
+                StateVersion::V1 => Contract::try_from_slice(&state).expect("Unknown state layout"),
+            }
+        } else {
+            Contract::from(ContractV0::try_from_slice(&state).expect("Unknown state layout"))
+        }
+    }
+    //Contract owner deploys the code passed as raw input and migrates the state
+    pub fn upgrade(&self) -> Promise {
+        self.assert_owner();
+        let gas_for_migrate = env::prepaid_gas()
+            .0
+            .saturating_sub(env::used_gas().0)
+            .saturating_sub(GAS_FOR_UPGRADE.0);
+        assert!(
+            gas_for_migrate >= MIN_GAS_FOR_MIGRATE.0,
+            "Not enough gas attached for the upgrade"
+        );
+        let code = env::input().expect("No code attached");
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call("migrate".to_string(), vec![], 0, Gas(gas_for_migrate))
+    }
+    pub fn state_version(&self) -> &StateVersion {
+        &self.state_version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    //writes the state the baseline contract left after accounts(1) created job "job"
+    fn write_v0_state() {
+        let entries: Vec<(Vec<u8>, Vec<u8>)> =
+            BorshDeserialize::try_from_slice(include_bytes!("../tests/fixtures/v0_state.borsh"))
+                .unwrap();
+        for (key, value) in entries.iter() {
+            env::storage_write(key, value);
+        }
+    }
+
+    #[test]
+    fn migrate_from_v0() {
+        testing_env!(VMContextBuilder::new().build());
+        write_v0_state();
+
+        let contract = Contract::migrate();
+
+        assert_eq!(contract.state_version, StateVersion::V1);
+        assert_eq!(contract.owner_id, accounts(0));
+        assert!(contract.pending_owner_id.is_none());
+        let job = contract.job_info("job".to_string()).unwrap();
+        assert_eq!(job.owner_id, accounts(1));
+        assert_eq!(job.metadata.reference, Some("bafkrei".to_string()));
+    }
+
+    #[test]
+    fn migrate_current_state_is_noop() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = Contract::new(accounts(0));
+        contract.pending_owner_id = Some(accounts(1));
+        env::state_write(&contract);
+
+        let migrated = Contract::migrate();
+
+        assert_eq!(migrated.state_version, StateVersion::V1);
+        assert_eq!(migrated.pending_owner_id, Some(accounts(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown state layout")]
+    fn migrate_unknown_state_panics() {
+        testing_env!(VMContextBuilder::new().build());
+        env::storage_write(b"STATE", &[1, 2, 3]);
+        Contract::migrate();
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough gas attached for the upgrade")]
+    fn upgrade_without_gas_for_migrate_panics() {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .prepaid_gas(GAS_FOR_UPGRADE)
+            .build());
+        let contract = Contract::new(accounts(0));
+        contract.upgrade();
+    }
+}
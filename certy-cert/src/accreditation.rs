@@ -0,0 +1,250 @@
+use crate::*;
+
+//maximum number of accreditation links followed when building a trust chain
+pub const MAX_TRUST_CHAIN_DEPTH: usize = 8;
+
+//Accreditation of an account by an accreditor
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Accreditation {
+    pub accreditor_id: AccountId, // account that granted the accreditation
+    pub scope: String,            // ex. "higher-education" or "cloud-computing-training"
+    pub issued_at: u64,           // When accreditation was granted, Unix epoch in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>, // When accreditation expires, Unix epoch in milliseconds
+}
+
+//The Json accreditation is what will be returned from view calls.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonAccreditation {
+    pub account_id: AccountId,
+    #[serde(flatten)]
+    pub accreditation: Accreditation,
+    //whether the accreditation is not expired
+    pub valid: bool,
+}
+
+//The trust chain from an issuer up to a root accreditor
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonTrustChain {
+    //accreditations from the issuer up to the last accreditor
+    pub links: Vec<JsonAccreditation>,
+    //root accreditor the chain ends at, if any
+    pub root_id: Option<AccountId>,
+    //whether the chain reaches a root accreditor and every link is currently valid
+    pub valid: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn root_accreditor_add(&mut self, account_id: AccountId) {
+        assert_at_least_one_yocto();
+        self.assert_admin();
+        let initial_storage_usage = env::storage_usage();
+        assert!(
+            self.root_accreditors.insert(&account_id),
+            "Already a root accreditor"
+        );
+        let root_accreditor_add_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::RootAccreditorAdd(vec![RootAccreditorLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_ids: vec![account_id.to_string()],
+            }]),
+        };
+        root_accreditor_add_log.emit();
+        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
+        refund_deposit(required_storage_in_bytes);
+    }
+    #[payable]
+    pub fn root_accreditor_remove(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_admin();
+        assert!(
+            self.root_accreditors.remove(&account_id),
+            "Not a root accreditor"
+        );
+        let root_accreditor_remove_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::RootAccreditorRemove(vec![RootAccreditorLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_ids: vec![account_id.to_string()],
+            }]),
+        };
+        root_accreditor_remove_log.emit();
+    }
+    //Root accreditors and accredited accounts can accredit other accounts within their own scope.
+    //Only the current accreditor can replace an accreditation, others have to wait for it to be revoked
+    #[payable]
+    pub fn accreditation_grant(
+        &mut self,
+        account_id: AccountId,
+        scope: String,
+        expires_at: Option<u64>,
+    ) {
+        assert_at_least_one_yocto();
+        let accreditor_id = env::predecessor_account_id();
+        if !self.root_accreditors.contains(&accreditor_id) {
+            let own_accreditation = self
+                .accreditations
+                .get(&accreditor_id)
+                .filter(accreditation_is_valid)
+                .expect("Not accreditor");
+            assert!(
+                scope_covers(&own_accreditation.scope, &scope),
+                "Scope outside of the accreditor scope"
+            );
+        }
+        assert_ne!(accreditor_id, account_id, "Can't accredit itself");
+        if let Some(old_accreditation) = self.accreditations.get(&account_id) {
+            assert_eq!(
+                old_accreditation.accreditor_id, accreditor_id,
+                "Already accredited"
+            );
+        }
+        let initial_storage_usage = env::storage_usage();
+        let accreditation = Accreditation {
+            accreditor_id,
+            scope,
+            issued_at: env::block_timestamp_ms(),
+            expires_at,
+        };
+        self.accreditations.insert(&account_id, &accreditation);
+        self.internal_accredited_add_to_accreditor(&accreditation.accreditor_id, &account_id);
+        let accreditation_grant_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::AccreditationGrant(vec![AccreditationGrantLog {
+                account_id: account_id.to_string(),
+                accreditation,
+            }]),
+        };
+        accreditation_grant_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    //The accreditor, the contract owner or an admin can revoke an accreditation
+    #[payable]
+    pub fn accreditation_revoke(&mut self, account_id: AccountId, reason: Option<String>) {
+        assert_one_yocto();
+        let accreditation = self
+            .accreditations
+            .get(&account_id)
+            .expect("No accreditation");
+        assert!(
+            env::predecessor_account_id() == accreditation.accreditor_id
+                || self.is_admin(env::predecessor_account_id()),
+            "Not accreditor"
+        );
+        let initial_storage_usage = env::storage_usage();
+        self.accreditations.remove(&account_id);
+        self.internal_accredited_remove_from_accreditor(&accreditation.accreditor_id, &account_id);
+        let accreditation_revoke_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::AccreditationRevoke(vec![AccreditationRevokeLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                accreditor_id: accreditation.accreditor_id.to_string(),
+                account_ids: vec![account_id.to_string()],
+                reason,
+            }]),
+        };
+        accreditation_revoke_log.emit();
+        //the accreditor paid for the accreditation, it gets the storage back
+        self.internal_storage_settle_from(&accreditation.accreditor_id, initial_storage_usage, 0);
+    }
+    pub fn accreditation_info(&self, account_id: AccountId) -> Option<JsonAccreditation> {
+        self.accreditations
+            .get(&account_id)
+            .map(|accreditation| JsonAccreditation {
+                account_id,
+                valid: accreditation_is_valid(&accreditation),
+                accreditation,
+            })
+    }
+    //Query for all the accounts accredited by an accreditor
+    pub fn accreditations_by_accreditor(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonAccreditation> {
+        let accredited_for_accreditor_set = self.accredited_per_accreditor.get(&account_id);
+        let accredited = if let Some(accredited_for_accreditor_set) = accredited_for_accreditor_set
+        {
+            accredited_for_accreditor_set
+        } else {
+            return vec![];
+        };
+
+        paginate(accredited.as_vector(), from_index, limit)
+            .items
+            .into_iter()
+            .map(|account_id| self.accreditation_info(account_id).unwrap())
+            .collect()
+    }
+    pub fn root_accreditors(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+        paginate(self.root_accreditors.as_vector(), from_index, limit).items
+    }
+    //Trust chain from an account up to a root accreditor
+    pub fn account_trust_chain(&self, account_id: AccountId) -> JsonTrustChain {
+        let mut links: Vec<JsonAccreditation> = vec![];
+        let mut current_id = account_id;
+        let mut root_id = None;
+        for _ in 0..=MAX_TRUST_CHAIN_DEPTH {
+            if self.root_accreditors.contains(&current_id) {
+                root_id = Some(current_id);
+                break;
+            }
+            //stop on a cycle or a missing accreditation
+            if links.iter().any(|link| link.account_id == current_id) {
+                break;
+            }
+            let link = match self.accreditation_info(current_id) {
+                Some(link) => link,
+                None => break,
+            };
+            current_id = link.accreditation.accreditor_id.clone();
+            links.push(link);
+        }
+        //every accreditation has to stay within the scope of the one above it
+        let valid = root_id.is_some()
+            && links.iter().all(|link| link.valid)
+            && links.windows(2).all(|pair| {
+                scope_covers(&pair[1].accreditation.scope, &pair[0].accreditation.scope)
+            });
+        JsonTrustChain {
+            links,
+            root_id,
+            valid,
+        }
+    }
+    //Trust chain from the owner of the category that issued the token up to a root accreditor
+    pub fn cert_trust_chain(&self, token_id: TokenId) -> Option<JsonTrustChain> {
+        let token = self.tokens_by_id.get(&token_id)?;
+        let category = self.categories_by_id.get(&token.category_id)?;
+        Some(self.account_trust_chain(category.owner_id))
+    }
+}
+
+//whether an accreditation is not expired
+pub(crate) fn accreditation_is_valid(accreditation: &Accreditation) -> bool {
+    accreditation
+        .expires_at
+        .map(|expires_at| env::block_timestamp_ms() < expires_at)
+        .unwrap_or(true)
+}
+
+//whether `scope` includes `sub_scope`: the same scope or one nested under it, ex. "higher-education"
+//includes "higher-education/engineering"
+pub(crate) fn scope_covers(scope: &str, sub_scope: &str) -> bool {
+    sub_scope == scope
+        || sub_scope
+            .strip_prefix(scope)
+            .map(|rest| rest.starts_with('/'))
+            .unwrap_or(false)
+}
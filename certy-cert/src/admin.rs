@@ -0,0 +1,126 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    pub fn owner_get(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+    pub fn owner_pending(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+    //First step of the ownership handover, the new owner has to accept it
+    #[payable]
+    pub fn owner_transfer_propose(&mut self, new_owner_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.pending_owner_id = Some(new_owner_id.clone());
+        let owner_transfer_propose_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::OwnerTransferPropose(vec![OwnerTransferLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                old_owner_id: self.owner_id.to_string(),
+                new_owner_id: new_owner_id.to_string(),
+            }]),
+        };
+        owner_transfer_propose_log.emit();
+    }
+    #[payable]
+    pub fn owner_transfer_accept(&mut self) {
+        assert_one_yocto();
+        let new_owner_id = self
+            .pending_owner_id
+            .take()
+            .expect("No pending owner transfer");
+        assert_eq!(
+            env::predecessor_account_id(),
+            new_owner_id,
+            "Not pending owner"
+        );
+        let old_owner_id = std::mem::replace(&mut self.owner_id, new_owner_id);
+        let owner_transfer_accept_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::OwnerTransferAccept(vec![OwnerTransferLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                old_owner_id: old_owner_id.to_string(),
+                new_owner_id: self.owner_id.to_string(),
+            }]),
+        };
+        owner_transfer_accept_log.emit();
+    }
+    #[payable]
+    pub fn admin_add(&mut self, account_id: AccountId) {
+        assert_at_least_one_yocto();
+        self.assert_owner();
+        let initial_storage_usage = env::storage_usage();
+        assert!(self.admins.insert(&account_id), "Already an admin");
+        let admin_add_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::AdminAdd(vec![AdminLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_ids: vec![account_id.to_string()],
+            }]),
+        };
+        admin_add_log.emit();
+        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
+        refund_deposit(required_storage_in_bytes);
+    }
+    #[payable]
+    pub fn admin_remove(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        assert!(self.admins.remove(&account_id), "Not an admin");
+        let admin_remove_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::AdminRemove(vec![AdminLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_ids: vec![account_id.to_string()],
+            }]),
+        };
+        admin_remove_log.emit();
+    }
+    pub fn admins(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+        paginate(self.admins.as_vector(), from_index, limit).items
+    }
+    pub fn is_admin(&self, account_id: AccountId) -> bool {
+        account_id == self.owner_id || self.admins.contains(&account_id)
+    }
+    #[payable]
+    pub fn nft_metadata_update(&mut self, metadata: NFTContractMetadata) {
+        assert_at_least_one_yocto();
+        self.assert_admin();
+        let old_metadata = self.metadata.get().unwrap();
+        let mut contract_metadata = old_metadata.clone();
+        // updatable fields
+        contract_metadata.name = metadata.name;
+        contract_metadata.icon = metadata.icon;
+        contract_metadata.base_uri = metadata.base_uri;
+        contract_metadata.reference = metadata.reference;
+        contract_metadata.reference_hash = metadata.reference_hash;
+
+        let initial_storage_usage = env::storage_usage();
+        self.metadata.set(&contract_metadata);
+        let contract_metadata_update_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::ContractMetadataUpdate(vec![ContractMetadataUpdateLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                old_metadata,
+                new_metadata: contract_metadata,
+            }]),
+        };
+        contract_metadata_update_log.emit();
+        let mut required_storage_in_bytes = 0;
+        if env::storage_usage() < initial_storage_usage {
+            let released_storage = initial_storage_usage - env::storage_usage();
+            Promise::new(env::predecessor_account_id())
+                .transfer(Balance::from(released_storage) * env::storage_byte_cost());
+        } else {
+            required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
+        }
+        refund_deposit(required_storage_in_bytes);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::*;
+
+//Holder contesting the revocation of a certificate
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Appeal {
+    pub holder_id: AccountId,       // holder who opened the appeal
+    pub evidence_reference: String, // URL to an off-chain document supporting the appeal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of the evidence document
+    pub opened_at: u64,             // When the appeal was opened, Unix epoch in milliseconds
+    pub responses: Vec<AppealResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision: Option<AppealDecision>,
+}
+
+//Issuer answer to an appeal
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppealResponse {
+    pub responder_id: AccountId,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>, // URL to an off-chain document backing the response
+    pub responded_at: u64, // When the response was given, Unix epoch in milliseconds
+}
+
+//Final ruling on an appeal, a granted appeal lifts the revocation
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppealDecision {
+    pub decided_by: AccountId, // category owner or arbiter
+    pub granted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub decided_at: u64, // When the decision was made, Unix epoch in milliseconds
+}
+
+#[near_bindgen]
+impl Contract {
+    //Category owner appoints the account deciding appeals on its certificates, none removes it
+    #[payable]
+    pub fn category_arbiter_set(&mut self, category_id: CategoryId, arbiter_id: Option<AccountId>) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        self.assert_category_owner(env::predecessor_account_id(), &category_id);
+        let initial_storage_usage = env::storage_usage();
+        if let Some(arbiter_id) = arbiter_id.as_ref() {
+            self.arbiters_per_category.insert(&category_id, arbiter_id);
+        } else {
+            self.arbiters_per_category.remove(&category_id);
+        }
+        let category_arbiter_update_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::CategoryArbiterUpdate(vec![CategoryArbiterUpdateLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                category_id: category_id.to_string(),
+                arbiter_id: arbiter_id.map(|arbiter_id| arbiter_id.to_string()),
+            }]),
+        };
+        category_arbiter_update_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    //Holder contests the revocation of a certificate, every revocation can be appealed once.
+    //Appeals are kept oldest first, a new one never replaces an earlier one
+    #[payable]
+    pub fn appeal_open(
+        &mut self,
+        token_id: TokenId,
+        evidence_reference: String,
+        evidence_hash: Option<Base64VecU8>,
+    ) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+        assert_eq!(
+            env::predecessor_account_id(),
+            token.owner_id,
+            "Not cert holder"
+        );
+        assert!(
+            self.internal_status_get(&token_id, StatusPurpose::Revocation),
+            "Cert not revoked"
+        );
+        let initial_storage_usage = env::storage_usage();
+        let mut appeals = self.internal_appeals_mut(&token_id);
+        //a granted appeal lifted an earlier revocation, this one can be appealed again
+        if let Some(appeal) = appeals.iter().next_back() {
+            assert!(
+                appeal
+                    .decision
+                    .map(|decision| decision.granted)
+                    .unwrap_or(false),
+                "Revocation already appealed"
+            );
+        }
+        appeals.push(&Appeal {
+            holder_id: token.owner_id.clone(),
+            evidence_reference: evidence_reference.clone(),
+            evidence_hash: evidence_hash.clone(),
+            opened_at: env::block_timestamp_ms(),
+            responses: vec![],
+            decision: None,
+        });
+        self.appeals_per_token.insert(&token_id, &appeals);
+        let appeal_open_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::AppealOpen(vec![AppealOpenLog {
+                owner_id: token.owner_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+                evidence_reference,
+                evidence_hash,
+            }]),
+        };
+        appeal_open_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    //Cert provider answers an open appeal
+    #[payable]
+    pub fn appeal_respond(
+        &mut self,
+        token_id: TokenId,
+        message: String,
+        reference: Option<String>,
+    ) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        self.assert_cert_provider(env::predecessor_account_id(), &token_id);
+        let initial_storage_usage = env::storage_usage();
+        let (mut appeals, mut appeal) = self.internal_open_appeal(&token_id);
+        appeal.responses.push(AppealResponse {
+            responder_id: env::predecessor_account_id(),
+            message: message.clone(),
+            reference: reference.clone(),
+            responded_at: env::block_timestamp_ms(),
+        });
+        appeals.replace(appeals.len() - 1, &appeal);
+        let appeal_respond_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::AppealRespond(vec![AppealRespondLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                token_ids: vec![token_id.to_string()],
+                message,
+                reference,
+            }]),
+        };
+        appeal_respond_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    //Category owner or its arbiter closes an appeal, granting it lifts the revocation
+    #[payable]
+    pub fn appeal_decide(&mut self, token_id: TokenId, granted: bool, reason: Option<String>) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        self.assert_token_not_frozen(&token_id);
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+        let category = self
+            .categories_by_id
+            .get(&token.category_id)
+            .expect("No Category");
+        let arbiter_id = self.arbiters_per_category.get(&token.category_id);
+        assert!(
+            env::predecessor_account_id() == category.owner_id
+                || Some(env::predecessor_account_id()) == arbiter_id,
+            "Not category owner or arbiter"
+        );
+        let initial_storage_usage = env::storage_usage();
+        let (mut appeals, mut appeal) = self.internal_open_appeal(&token_id);
+        appeal.decision = Some(AppealDecision {
+            decided_by: env::predecessor_account_id(),
+            granted,
+            reason: reason.clone(),
+            decided_at: env::block_timestamp_ms(),
+        });
+        appeals.replace(appeals.len() - 1, &appeal);
+        if granted {
+            self.internal_status_set(&token_id, StatusPurpose::Revocation, false);
+        }
+        let appeal_decide_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::AppealDecide(vec![AppealDecideLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                token_ids: vec![token_id.to_string()],
+                granted,
+                reason,
+            }]),
+        };
+        appeal_decide_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    //Latest appeal against the revocation of a certificate
+    pub fn cert_appeal(&self, token_id: TokenId) -> Option<Appeal> {
+        self.appeals_per_token
+            .get(&token_id)
+            .and_then(|appeals| appeals.iter().next_back())
+    }
+    //Every appeal against the revocations of a certificate, oldest first
+    pub fn cert_appeals(
+        &self,
+        token_id: TokenId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Page<Appeal> {
+        match self.appeals_per_token.get(&token_id) {
+            Some(appeals) => paginate(&appeals, from_index, limit),
+            None => Page::empty(),
+        }
+    }
+    pub fn category_arbiter(&self, category_id: CategoryId) -> Option<AccountId> {
+        self.arbiters_per_category.get(&category_id)
+    }
+}
+
+impl Contract {
+    //the appeals of a token, insert the vector back into `appeals_per_token` after pushing to it
+    pub(crate) fn internal_appeals_mut(&mut self, token_id: &TokenId) -> Vector<Appeal> {
+        self.appeals_per_token.get(token_id).unwrap_or_else(|| {
+            Vector::new(
+                StorageKey::AppealsPerTokenInner {
+                    //we get a new unique prefix for the collection
+                    token_id_hash: hash_token_id(token_id),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        })
+    }
+    //the appeals of a token along with the latest one, which must still be waiting for a decision
+    fn internal_open_appeal(&mut self, token_id: &TokenId) -> (Vector<Appeal>, Appeal) {
+        let appeals = self.internal_appeals_mut(token_id);
+        let appeal = appeals.iter().next_back().expect("No appeal");
+        assert!(appeal.decision.is_none(), "Appeal already decided");
+        (appeals, appeal)
+    }
+    //remove every appeal of a token, each holder gets back the storage it paid for
+    pub(crate) fn internal_appeals_release(&mut self, token_id: &TokenId) {
+        let mut appeals = match self.appeals_per_token.get(token_id) {
+            Some(appeals) => appeals,
+            None => return,
+        };
+        while let Some(appeal) = appeals.iter().next_back() {
+            let initial_storage_usage = env::storage_usage();
+            appeals.pop();
+            if appeals.is_empty() {
+                self.appeals_per_token.remove(token_id);
+            }
+            self.internal_storage_settle_from(&appeal.holder_id, initial_storage_usage, 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::context;
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    //category "1" of accounts(1) with certificate "0" minted to accounts(2) and revoked
+    fn setup() -> Contract {
+        let mut contract = crate::test_utils::setup();
+        revoke(&mut contract);
+        contract
+    }
+
+    fn revoke(contract: &mut Contract) {
+        testing_env!(context(1, 1).build());
+        contract.cert_revoke("0".to_string(), None);
+    }
+
+    fn open(contract: &mut Contract, evidence_reference: &str) {
+        testing_env!(context(2, 10u128.pow(24)).build());
+        contract.appeal_open("0".to_string(), evidence_reference.to_string(), None);
+    }
+
+    fn decide(contract: &mut Contract, granted: bool) {
+        testing_env!(context(1, 10u128.pow(24)).build());
+        contract.appeal_decide("0".to_string(), granted, None);
+    }
+
+    #[test]
+    fn appeal_after_granted_appeal_keeps_the_earlier_one() {
+        let mut contract = setup();
+        open(&mut contract, "https://example.com/first.pdf");
+        decide(&mut contract, true);
+        revoke(&mut contract);
+        open(&mut contract, "https://example.com/second.pdf");
+
+        let page = contract.cert_appeals("0".to_string(), None, None);
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(
+            page.items[0].evidence_reference,
+            "https://example.com/first.pdf"
+        );
+        assert!(page.items[0].decision.as_ref().unwrap().granted);
+        assert_eq!(
+            page.items[1].evidence_reference,
+            "https://example.com/second.pdf"
+        );
+        assert!(page.items[1].decision.is_none());
+        assert_eq!(
+            contract
+                .cert_appeal("0".to_string())
+                .unwrap()
+                .evidence_reference,
+            "https://example.com/second.pdf"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Revocation already appealed")]
+    fn appeal_after_denied_appeal_panics() {
+        let mut contract = setup();
+        open(&mut contract, "https://example.com/first.pdf");
+        decide(&mut contract, false);
+        open(&mut contract, "https://example.com/second.pdf");
+    }
+
+    #[test]
+    #[should_panic(expected = "Category frozen")]
+    fn appeal_decide_on_frozen_category_panics() {
+        let mut contract = setup();
+        open(&mut contract, "https://example.com/first.pdf");
+        testing_env!(context(0, 10u128.pow(24)).build());
+        contract.category_freeze("1".to_string(), None);
+        decide(&mut contract, true);
+    }
+
+    #[test]
+    fn cert_delete_refunds_appeal_storage_to_the_holder() {
+        let mut contract = setup();
+        testing_env!(context(2, 10u128.pow(24)).build());
+        contract.storage_deposit(None, None);
+        let initial_available = contract
+            .storage_balance_of(accounts(2))
+            .unwrap()
+            .available
+            .0;
+        open(&mut contract, "https://example.com/first.pdf");
+        decide(&mut contract, true);
+        revoke(&mut contract);
+        open(&mut contract, "https://example.com/second.pdf");
+        let opened_usage = env::storage_usage();
+
+        testing_env!(context(1, 1).storage_usage(opened_usage).build());
+        contract.cert_delete("0".to_string());
+
+        assert!(contract.appeals_per_token.get(&"0".to_string()).is_none());
+        assert!(
+            contract
+                .storage_balance_of(accounts(2))
+                .unwrap()
+                .available
+                .0
+                > initial_available
+        );
+    }
+}
@@ -42,4 +42,6 @@ pub struct JsonCategory {
     pub owner_id: AccountId,
     //category metadata
     pub metadata: CategoryMetadata,
+    //whether the owner of the category is a verified institution
+    pub verified: bool,
 }
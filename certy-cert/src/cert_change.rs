@@ -0,0 +1,158 @@
+use crate::*;
+
+//A pending change on a certificate that the holder has not acknowledged yet
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CertChange {
+    //account that updated the certificate
+    pub authorized_id: AccountId,
+    //reason given by the issuer for the update
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    //when the certificate was updated, Unix epoch in milliseconds
+    pub updated_at: u64,
+}
+
+//The Json cert change is what will be returned from view calls.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonCertChange {
+    pub token_id: TokenId,
+    //unacknowledged changes on the certificate, oldest first
+    pub changes: Vec<CertChange>,
+}
+
+#[near_bindgen]
+impl Contract {
+    //Holder acknowledges the changes made on their certificates, all of them if no token IDs are given
+    #[payable]
+    pub fn cert_changes_acknowledge(&mut self, token_ids: Option<Vec<TokenId>>) {
+        assert_one_yocto();
+        let holder_id = env::predecessor_account_id();
+        let token_ids = token_ids.unwrap_or_else(|| {
+            self.cert_changes_per_holder
+                .get(&holder_id)
+                .map(|changes| changes.keys().collect())
+                .unwrap_or_default()
+        });
+        for token_id in token_ids.iter() {
+            //each change was paid with the storage of the certificate when updating it,
+            //whoever paid for the certificate gets the storage back
+            loop {
+                let initial_storage_usage = env::storage_usage();
+                let change = match self.internal_cert_change_pop(&holder_id, token_id) {
+                    Some(change) => change,
+                    None => break,
+                };
+                let payer_id = self
+                    .cert_storage_payer(token_id.clone())
+                    .unwrap_or_else(|| change.authorized_id.clone());
+                self.internal_storage_release_from(
+                    &payer_id,
+                    &change.authorized_id,
+                    initial_storage_usage,
+                    0,
+                );
+            }
+        }
+    }
+    //Query for the unacknowledged changes on the certificates of a holder, grouped by certificate
+    pub fn cert_changes_for_holder(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonCertChange> {
+        let changes_for_holder = self.cert_changes_per_holder.get(&account_id);
+        let changes = if let Some(changes_for_holder) = changes_for_holder {
+            changes_for_holder
+        } else {
+            return vec![];
+        };
+
+        paginate_map(&changes, from_index, limit)
+            .items
+            .into_iter()
+            .map(|(token_id, changes)| JsonCertChange { token_id, changes })
+            .collect()
+    }
+    //Number of certificates of a holder with unacknowledged changes
+    pub fn cert_changes_supply_for_holder(&self, account_id: AccountId) -> U128 {
+        if let Some(changes_for_holder) = self.cert_changes_per_holder.get(&account_id) {
+            U128(changes_for_holder.len() as u128)
+        } else {
+            U128(0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{context, setup, token_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    #[test]
+    fn test_cert_changes_kept_until_acknowledged() {
+        let mut contract = setup();
+        contract.cert_update(
+            token_metadata(),
+            "0".to_string(),
+            Some("Fix typo".to_string()),
+        );
+        contract.cert_update(
+            token_metadata(),
+            "0".to_string(),
+            Some("Fix date".to_string()),
+        );
+
+        assert_eq!(
+            contract.cert_changes_supply_for_holder(accounts(2)),
+            U128(1)
+        );
+        let changes = contract.cert_changes_for_holder(accounts(2), None, None);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].token_id, "0");
+        let reasons: Vec<Option<String>> = changes[0]
+            .changes
+            .iter()
+            .map(|change| change.reason.clone())
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![Some("Fix typo".to_string()), Some("Fix date".to_string())]
+        );
+        assert!(changes[0]
+            .changes
+            .iter()
+            .all(|change| change.authorized_id == accounts(1)));
+
+        testing_env!(context(2, 1).build());
+        contract.cert_changes_acknowledge(None);
+        assert_eq!(
+            contract.cert_changes_supply_for_holder(accounts(2)),
+            U128(0)
+        );
+        assert!(contract
+            .cert_changes_for_holder(accounts(2), None, None)
+            .is_empty());
+        assert!(contract.cert_changes_per_holder.get(&accounts(2)).is_none());
+    }
+
+    #[test]
+    fn test_cert_changes_acknowledge_given_tokens() {
+        let mut contract = setup();
+        contract.nft_mint(token_metadata(), accounts(2), "1".to_string());
+        contract.cert_update(token_metadata(), "0".to_string(), None);
+        contract.cert_update(token_metadata(), "1".to_string(), None);
+        contract.cert_update(token_metadata(), "1".to_string(), None);
+
+        testing_env!(context(2, 1).build());
+        contract.cert_changes_acknowledge(Some(vec!["1".to_string()]));
+        let changes = contract.cert_changes_for_holder(accounts(2), None, None);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].token_id, "0");
+        assert_eq!(changes[0].changes.len(), 1);
+    }
+}
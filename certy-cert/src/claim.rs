@@ -0,0 +1,569 @@
+use crate::*;
+use near_sdk::{ext_contract, serde_json, Gas, PromiseOrValue, PromiseResult};
+
+//gas attached to every payout made in a NEP-141 token
+const GAS_FOR_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+//gas attached to resolving a payout once the transfer is done
+const GAS_FOR_RESOLVE_PAYOUT: Gas = Gas(5_000_000_000_000);
+//basis points making up the whole price
+const FEE_BPS_DENOMINATOR: u128 = 10_000;
+
+#[ext_contract(ext_ft)]
+trait FungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+#[ext_contract(ext_payout_resolver)]
+trait FtPayoutResolver {
+    fn ft_resolve_payout(
+        &mut self,
+        receiver_id: AccountId,
+        ft_contract_id: AccountId,
+        amount: U128,
+    ) -> bool;
+}
+
+pub trait FtPayoutResolver {
+    fn ft_resolve_payout(
+        &mut self,
+        receiver_id: AccountId,
+        ft_contract_id: AccountId,
+        amount: U128,
+    ) -> bool;
+}
+
+pub trait FungibleTokenReceiver {
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128>;
+}
+
+//Price recipients pay to claim a certificate of a category
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct CategoryPrice {
+    //NEP-141 contract of the token the price is paid in, NEAR if none
+    pub ft_contract_id: Option<AccountId>,
+    pub amount: Balance,
+    //metadata of the certificates minted on claim
+    pub metadata: TokenMetadata,
+}
+
+//The Json category price is what will be returned from view calls.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonCategoryPrice {
+    pub ft_contract_id: Option<AccountId>,
+    pub amount: U128,
+    pub metadata: TokenMetadata,
+}
+
+//`msg` expected by `ft_on_transfer`
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CertClaimMsg {
+    pub category_id: CategoryId,
+}
+
+#[near_bindgen]
+impl Contract {
+    //Category owner sets the price of claiming a certificate, no price disables claims
+    #[payable]
+    pub fn category_price_set(
+        &mut self,
+        category_id: CategoryId,
+        price: Option<JsonCategoryPrice>,
+    ) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        self.assert_category_owner(env::predecessor_account_id(), &category_id);
+        self.assert_category_not_frozen(&category_id);
+        let initial_storage_usage = env::storage_usage();
+        if let Some(price) = price.as_ref() {
+            if let Some(ft_contract_id) = price.ft_contract_id.as_ref() {
+                assert!(
+                    self.ft_whitelist.contains(ft_contract_id),
+                    "Token not whitelisted"
+                );
+            }
+            self.category_prices.insert(
+                &category_id,
+                &CategoryPrice {
+                    ft_contract_id: price.ft_contract_id.clone(),
+                    amount: price.amount.0,
+                    metadata: price.metadata.clone(),
+                },
+            );
+        } else {
+            self.category_prices.remove(&category_id);
+        }
+        let category_price_update_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::CategoryPriceUpdate(vec![CategoryPriceUpdateLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                category_id: category_id.to_string(),
+                price,
+            }]),
+        };
+        category_price_update_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    //Recipient pays the NEAR price of a category and gets the certificate,
+    //the deposit left after the price pays the storage
+    #[payable]
+    pub fn cert_claim(&mut self, category_id: CategoryId) -> TokenId {
+        let price = self
+            .category_prices
+            .get(&category_id)
+            .expect("Category has no claim price");
+        assert!(price.ft_contract_id.is_none(), "Price is not in NEAR");
+        assert!(
+            env::attached_deposit() >= price.amount,
+            "Must attach {} yoctoNEAR of price on top of storage",
+            price.amount,
+        );
+        let receiver_id = env::predecessor_account_id();
+        self.internal_cert_claim(
+            receiver_id.clone(),
+            category_id,
+            receiver_id,
+            env::attached_deposit() - price.amount,
+        )
+    }
+    #[payable]
+    pub fn ft_whitelist_add(&mut self, ft_contract_id: AccountId) {
+        assert_at_least_one_yocto();
+        self.assert_admin();
+        let initial_storage_usage = env::storage_usage();
+        assert!(
+            self.ft_whitelist.insert(&ft_contract_id),
+            "Token already whitelisted"
+        );
+        let ft_whitelist_add_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::FtWhitelistAdd(vec![FtWhitelistLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                ft_contract_ids: vec![ft_contract_id.to_string()],
+            }]),
+        };
+        ft_whitelist_add_log.emit();
+        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
+        refund_deposit(required_storage_in_bytes);
+    }
+    //Categories already priced in the token keep their price, new prices can't use it
+    #[payable]
+    pub fn ft_whitelist_remove(&mut self, ft_contract_id: AccountId) {
+        assert_one_yocto();
+        self.assert_admin();
+        assert!(
+            self.ft_whitelist.remove(&ft_contract_id),
+            "Token not whitelisted"
+        );
+        let ft_whitelist_remove_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::FtWhitelistRemove(vec![FtWhitelistLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                ft_contract_ids: vec![ft_contract_id.to_string()],
+            }]),
+        };
+        ft_whitelist_remove_log.emit();
+    }
+    pub fn ft_whitelist(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+        paginate(self.ft_whitelist.as_vector(), from_index, limit).items
+    }
+    //Take out the NEP-141 payouts held for the caller after a failed transfer,
+    //all of them if no amount is given
+    #[payable]
+    pub fn ft_payout_withdraw(
+        &mut self,
+        ft_contract_id: AccountId,
+        amount: Option<U128>,
+    ) -> Promise {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let key = (account_id.clone(), ft_contract_id.clone());
+        let held = self.ft_payouts.get(&key).unwrap_or(0);
+        let amount = amount.map(|amount| amount.0).unwrap_or(held);
+        assert!(
+            amount > 0 && amount <= held,
+            "Can't withdraw more than the held payout"
+        );
+        if amount == held {
+            self.ft_payouts.remove(&key);
+        } else {
+            self.ft_payouts.insert(&key, &(held - amount));
+        }
+        let ft_payout_withdraw_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::FtPayoutWithdraw(vec![FtPayoutLog {
+                account_id: account_id.to_string(),
+                ft_contract_id: ft_contract_id.to_string(),
+                amount: U128(amount),
+            }]),
+        };
+        ft_payout_withdraw_log.emit();
+        //a failed withdrawal is held again
+        self.internal_ft_payout(ft_contract_id, account_id, amount)
+    }
+    pub fn ft_payout_balance(&self, account_id: AccountId, ft_contract_id: AccountId) -> U128 {
+        U128(
+            self.ft_payouts
+                .get(&(account_id, ft_contract_id))
+                .unwrap_or(0),
+        )
+    }
+    pub fn category_price(&self, category_id: CategoryId) -> Option<JsonCategoryPrice> {
+        self.category_prices
+            .get(&category_id)
+            .map(|price| JsonCategoryPrice {
+                ft_contract_id: price.ft_contract_id,
+                amount: U128(price.amount),
+                metadata: price.metadata,
+            })
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for Contract {
+    //Recipient pays the price of a category with `ft_transfer_call` and gets the certificate,
+    //the storage is paid by the category owner. Any failure panics so the token contract
+    //refunds the whole amount, the part above the price is returned as unused
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        let ft_contract_id = env::predecessor_account_id();
+        assert!(
+            self.ft_whitelist.contains(&ft_contract_id),
+            "Token not whitelisted"
+        );
+        let claim_msg: CertClaimMsg = serde_json::from_str(&msg).expect("Invalid claim msg");
+        let price = self
+            .category_prices
+            .get(&claim_msg.category_id)
+            .expect("Category has no claim price");
+        assert_eq!(
+            price.ft_contract_id,
+            Some(ft_contract_id),
+            "Price is not in this token"
+        );
+        assert!(
+            amount.0 >= price.amount,
+            "Must transfer at least {} of the token",
+            price.amount,
+        );
+        let category = self
+            .categories_by_id
+            .get(&claim_msg.category_id)
+            .expect("No Category");
+        self.internal_cert_claim(sender_id, claim_msg.category_id, category.owner_id, 0);
+        PromiseOrValue::Value(U128(amount.0 - price.amount))
+    }
+}
+
+#[near_bindgen]
+impl FtPayoutResolver for Contract {
+    //Hold the payout for the receiver to withdraw with `ft_payout_withdraw` if the transfer failed,
+    //ex. the receiver is not registered with the token contract. The contract pays the storage
+    //of the held payout since callbacks carry no deposit
+    #[private]
+    fn ft_resolve_payout(
+        &mut self,
+        receiver_id: AccountId,
+        ft_contract_id: AccountId,
+        amount: U128,
+    ) -> bool {
+        if let PromiseResult::Successful(_) = env::promise_result(0) {
+            return true;
+        }
+        let key = (receiver_id.clone(), ft_contract_id.clone());
+        let held = self.ft_payouts.get(&key).unwrap_or(0);
+        self.ft_payouts.insert(&key, &(held + amount.0));
+        let ft_payout_hold_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::FtPayoutHold(vec![FtPayoutLog {
+                account_id: receiver_id.to_string(),
+                ft_contract_id: ft_contract_id.to_string(),
+                amount,
+            }]),
+        };
+        ft_payout_hold_log.emit();
+        false
+    }
+}
+
+impl Contract {
+    //transfer a NEP-141 payout, it is held for the receiver if the transfer fails
+    fn internal_ft_payout(
+        &self,
+        ft_contract_id: AccountId,
+        receiver_id: AccountId,
+        amount: Balance,
+    ) -> Promise {
+        ext_ft::ft_transfer(
+            receiver_id.clone(),
+            U128(amount),
+            None,
+            ft_contract_id.clone(),
+            1,
+            GAS_FOR_FT_TRANSFER,
+        )
+        .then(ext_payout_resolver::ft_resolve_payout(
+            receiver_id,
+            ft_contract_id,
+            U128(amount),
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_PAYOUT,
+        ))
+    }
+    //mint the priced certificate of a category to the recipient and pay the category owner
+    //the price minus the platform fee, `storage_account_id` pays the storage from `storage_deposit`
+    fn internal_cert_claim(
+        &mut self,
+        receiver_id: AccountId,
+        category_id: CategoryId,
+        storage_account_id: AccountId,
+        storage_deposit: Balance,
+    ) -> TokenId {
+        self.assert_not_paused();
+        self.assert_category_not_frozen(&category_id);
+        let price = self.category_prices.get(&category_id).unwrap();
+        let category = self
+            .categories_by_id
+            .get(&category_id)
+            .expect("No Category");
+
+        let initial_storage_usage = env::storage_usage();
+        let token_id =
+            self.internal_mint_token(receiver_id.clone(), price.metadata, category_id.clone());
+        let payer_id = self.internal_storage_payer(
+            &storage_account_id,
+            env::storage_usage() - initial_storage_usage + STORAGE_PAYER_ENTRY_BYTES,
+        );
+        //the category owner is the default payer of a certificate
+        if payer_id != category.owner_id {
+            self.storage_payer_by_token.insert(&token_id, &payer_id);
+        }
+        self.internal_storage_charge(
+            &payer_id,
+            &storage_account_id,
+            initial_storage_usage,
+            storage_deposit,
+        );
+
+        //verified issuers don't pay the platform fee
+        let fee = if self.verified_issuers.get(&category.owner_id).is_some() {
+            0
+        } else {
+            price.amount * u128::from(self.fee_config.claim_fee_bps) / FEE_BPS_DENOMINATOR
+        };
+        let payout = price.amount - fee;
+        if let Some(ft_contract_id) = price.ft_contract_id.clone() {
+            //fees in a token go straight to the contract owner
+            if payout > 0 {
+                self.internal_ft_payout(ft_contract_id.clone(), category.owner_id.clone(), payout);
+            }
+            if fee > 0 {
+                self.internal_ft_payout(ft_contract_id, self.owner_id.clone(), fee);
+            }
+        } else {
+            self.fees_collected += fee;
+            if payout > 0 {
+                Promise::new(category.owner_id.clone()).transfer(payout);
+            }
+        }
+
+        let cert_claim_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::CertClaim(vec![CertClaimLog {
+                owner_id: receiver_id.to_string(),
+                category_id: category_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+                ft_contract_id: price.ft_contract_id.map(|id| id.to_string()),
+                amount: U128(price.amount),
+                fee: U128(fee),
+            }]),
+        };
+        cert_claim_log.emit();
+        token_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{category_metadata, context, token_metadata};
+    use near_sdk::mock::VmAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts};
+    use near_sdk::{testing_env, RuntimeFeesConfig, VMConfig};
+
+    const PRICE: Balance = 1_000;
+    const NEAR_PRICE: Balance = 10u128.pow(24);
+
+    //category "1" of accounts(1) priced at `PRICE` of the token of accounts(3), with a 10% claim fee
+    fn setup() -> Contract {
+        testing_env!(context(0, 1).build());
+        let mut contract = Contract::new_default_meta(accounts(0));
+        contract.fee_config_set(JsonFeeConfig {
+            mint_fee: U128(0),
+            category_create_fee: U128(0),
+            claim_fee_bps: 1_000,
+        });
+        testing_env!(context(0, 10u128.pow(22)).build());
+        contract.ft_whitelist_add(accounts(3));
+        testing_env!(context(1, 10u128.pow(24)).build());
+        contract.storage_deposit(None, None);
+        contract.category_create("1".to_string(), category_metadata());
+        testing_env!(context(1, 1).build());
+        contract.category_price_set(
+            "1".to_string(),
+            Some(JsonCategoryPrice {
+                ft_contract_id: Some(accounts(3)),
+                amount: U128(PRICE),
+                metadata: token_metadata(),
+            }),
+        );
+        contract
+    }
+
+    fn claim(contract: &mut Contract, amount: Balance) -> U128 {
+        testing_env!(context(3, 0).build());
+        let msg = r#"{"category_id":"1"}"#.to_string();
+        match contract.ft_on_transfer(accounts(2), U128(amount), msg) {
+            PromiseOrValue::Value(unused) => unused,
+            PromiseOrValue::Promise(_) => panic!("Expected the unused amount"),
+        }
+    }
+
+    #[test]
+    fn ft_on_transfer_exact_amount() {
+        let mut contract = setup();
+        assert_eq!(claim(&mut contract, PRICE).0, 0);
+        assert_eq!(contract.nft_supply_for_owner(accounts(2)).0, 1);
+        //one payout to the category owner and one fee to the contract owner, each with its resolve
+        assert_eq!(get_created_receipts().len(), 4);
+    }
+
+    #[test]
+    fn ft_on_transfer_overpayment() {
+        let mut contract = setup();
+        assert_eq!(claim(&mut contract, PRICE + 250).0, 250);
+        assert_eq!(contract.nft_supply_for_owner(accounts(2)).0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Token not whitelisted")]
+    fn ft_on_transfer_unwhitelisted_token() {
+        let mut contract = setup();
+        testing_env!(context(4, 0).build());
+        contract.ft_on_transfer(
+            accounts(2),
+            U128(PRICE),
+            r#"{"category_id":"1"}"#.to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Must transfer at least 1000 of the token")]
+    fn ft_on_transfer_underpayment() {
+        let mut contract = setup();
+        claim(&mut contract, PRICE - 1);
+    }
+
+    #[test]
+    fn failed_payout_is_held_for_withdrawal() {
+        let mut contract = setup();
+        claim(&mut contract, PRICE);
+        testing_env!(
+            context(0, 0).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        assert!(!contract.ft_resolve_payout(accounts(1), accounts(3), U128(900)));
+        assert_eq!(contract.ft_payout_balance(accounts(1), accounts(3)).0, 900);
+
+        testing_env!(
+            context(0, 0).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+        assert!(contract.ft_resolve_payout(accounts(0), accounts(3), U128(100)));
+        assert_eq!(contract.ft_payout_balance(accounts(0), accounts(3)).0, 0);
+
+        testing_env!(context(1, 1).build());
+        contract.ft_payout_withdraw(accounts(3), Some(U128(400)));
+        assert_eq!(contract.ft_payout_balance(accounts(1), accounts(3)).0, 500);
+        contract.ft_payout_withdraw(accounts(3), None);
+        assert_eq!(contract.ft_payout_balance(accounts(1), accounts(3)).0, 0);
+    }
+
+    //same as `setup` with the price in NEAR
+    fn near_setup() -> Contract {
+        let mut contract = setup();
+        contract.category_price_set(
+            "1".to_string(),
+            Some(JsonCategoryPrice {
+                ft_contract_id: None,
+                amount: U128(NEAR_PRICE),
+                metadata: token_metadata(),
+            }),
+        );
+        contract
+    }
+
+    //yoctoNEAR transferred to an account by the receipts of the last call
+    fn transferred_to(account_id: AccountId) -> Balance {
+        get_created_receipts()
+            .into_iter()
+            .filter(|receipt| receipt.receiver_id == account_id)
+            .flat_map(|receipt| receipt.actions)
+            .map(|action| match action {
+                VmAction::Transfer { deposit } => deposit,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn cert_claim_pays_price_and_storage_and_refunds_the_rest() {
+        let mut contract = near_setup();
+        let storage_deposit = 10u128.pow(23);
+        testing_env!(context(2, NEAR_PRICE + storage_deposit).build());
+        let initial_storage_usage = env::storage_usage();
+        let token_id = contract.cert_claim("1".to_string());
+        let storage_cost =
+            Balance::from(env::storage_usage() - initial_storage_usage) * env::storage_byte_cost();
+
+        assert_eq!(contract.nft_token(token_id).unwrap().owner_id, accounts(2));
+        //the 10% fee stays with the contract, the rest of the price goes to the category owner
+        assert_eq!(contract.fees_collected, NEAR_PRICE / 10);
+        assert_eq!(transferred_to(accounts(1)), NEAR_PRICE - NEAR_PRICE / 10);
+        //the claimer pays its storage from the deposit left after the price
+        assert_eq!(transferred_to(accounts(2)), storage_deposit - storage_cost);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Must attach 1000000000000000000000000 yoctoNEAR of price on top of storage"
+    )]
+    fn cert_claim_underpayment_panics() {
+        let mut contract = near_setup();
+        testing_env!(context(2, NEAR_PRICE - 1).build());
+        contract.cert_claim("1".to_string());
+    }
+}
@@ -0,0 +1,290 @@
+use crate::*;
+
+pub const VC_CONTEXT_V1: &str = "https://www.w3.org/2018/credentials/v1";
+//vocabulary of the Certy terms the W3C contexts don't define, ex. `categoryId` or `tokenId`
+pub const CERTY_VOCAB: &str = "https://certy.network/vocab#";
+pub const CERTY_CERT_STATUS_TYPE: &str = "CertyCertStatus";
+
+//did:near identifier of an account
+pub(crate) fn did_near(account_id: &AccountId) -> String {
+    format!("did:near:{}", account_id)
+}
+
+//An entry of `@context`: a context URL or an inline context
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(untagged)]
+pub enum JsonLdContext {
+    Url(String),
+    Vocab {
+        #[serde(rename = "@vocab")]
+        vocab: String,
+    },
+}
+
+//`@context` of a credential: the given context URLs, then the Certy vocabulary
+//for the terms they leave undefined
+pub(crate) fn credential_context(urls: &[&str]) -> Vec<JsonLdContext> {
+    urls.iter()
+        .map(|url| JsonLdContext::Url(url.to_string()))
+        .chain(std::iter::once(JsonLdContext::Vocab {
+            vocab: CERTY_VOCAB.to_string(),
+        }))
+        .collect()
+}
+
+//RFC 3339 UTC date time of a Unix epoch in milliseconds, ex. "2023-01-01T00:00:00Z"
+pub(crate) fn rfc3339_from_ms(ms: u64) -> String {
+    let secs = ms / 1000;
+    let (hour, minute, second) = ((secs / 3600) % 24, (secs / 60) % 60, secs % 60);
+    //civil date from the days since 1970-01-01, in 400-year eras starting on March 1st
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, hour, minute, second
+    )
+}
+
+//W3C Verifiable Credential (VC Data Model 1.1) rendering of a certificate, unsigned:
+//the contract state is the proof
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<JsonLdContext>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub credential_type: Vec<String>,
+    pub issuer: CredentialIssuer,
+    pub issuance_date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_date: Option<String>,
+    pub credential_subject: CredentialSubject,
+    pub credential_status: Vec<CredentialStatus>,
+}
+
+//Owner of the category the certificate belongs to
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CredentialIssuer {
+    pub id: String,
+    //display name of the institution, when verified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+//Holder of the certificate and what it certifies
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialSubject {
+    pub id: String,
+    pub category_id: CategoryId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_hash: Option<Base64VecU8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_hash: Option<Base64VecU8>,
+}
+
+//Where a verifier checks that the certificate is still held and valid, a StatusList2021 entry
+//when the certificate has a status index
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialStatus {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub status_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_purpose: Option<StatusPurpose>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_list_index: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_list_credential: Option<String>,
+    pub contract_id: AccountId,
+    pub token_id: TokenId,
+}
+
+#[near_bindgen]
+impl Contract {
+    //Render a certificate as a W3C Verifiable Credential
+    pub fn cert_credential(&self, token_id: TokenId) -> Option<VerifiableCredential> {
+        let token = self.nft_token(token_id.clone())?;
+        let category = self.category_info(token.category_id.clone()).unwrap();
+        let issuer_name = self
+            .verified_issuers
+            .get(&category.owner_id)
+            .map(|verification| verification.display_name);
+        let metadata = token.metadata;
+        let contract_id = env::current_account_id();
+        let id = format!("{}/certs/{}", did_near(&contract_id), token_id);
+        let credential_status = self.internal_credential_status(&token.category_id, &token_id);
+
+        Some(VerifiableCredential {
+            context: credential_context(&[VC_CONTEXT_V1]),
+            id: id.clone(),
+            credential_type: vec![
+                "VerifiableCredential".to_string(),
+                "CertyCertificate".to_string(),
+            ],
+            issuer: CredentialIssuer {
+                id: did_near(&category.owner_id),
+                name: issuer_name,
+            },
+            issuance_date: rfc3339_from_ms(metadata.issued_at.unwrap_or(0)),
+            expiration_date: metadata.expires_at.map(rfc3339_from_ms),
+            credential_subject: CredentialSubject {
+                id: did_near(&token.owner_id),
+                category_id: token.category_id,
+                category_title: category.metadata.title,
+                title: metadata.title,
+                description: metadata.description,
+                media: metadata.media,
+                media_hash: metadata.media_hash,
+                valid_from: metadata.starts_at.map(rfc3339_from_ms),
+                extra: metadata.extra,
+                reference: metadata.reference,
+                reference_hash: metadata.reference_hash,
+            },
+            credential_status,
+        })
+    }
+    //Admin sets the URL status lists are served under, so verifiers can fetch
+    //the `statusListCredential` of a certificate. None keeps the did:near path
+    #[payable]
+    pub fn credential_base_url_set(&mut self, base_url: Option<String>) {
+        assert_at_least_one_yocto();
+        self.assert_admin();
+        let base_url = base_url.map(|base_url| base_url.trim_end_matches('/').to_string());
+        if let Some(base_url) = base_url.as_ref() {
+            assert!(
+                base_url.starts_with("https://"),
+                "Base URL must be an https URL"
+            );
+        }
+        let initial_storage_usage = env::storage_usage();
+        self.credential_base_url = base_url.clone();
+        let credential_base_url_update_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::CredentialBaseUrlUpdate(vec![CredentialBaseUrlUpdateLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                base_url,
+            }]),
+        };
+        credential_base_url_update_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    pub fn credential_base_url(&self) -> Option<String> {
+        self.credential_base_url.clone()
+    }
+}
+
+impl Contract {
+    //URL of the status list credential of a category, under the credential base URL if set
+    pub(crate) fn internal_status_list_url(
+        &self,
+        category_id: &CategoryId,
+        purpose: StatusPurpose,
+    ) -> String {
+        let base_url = self
+            .credential_base_url
+            .clone()
+            .unwrap_or_else(|| did_near(&env::current_account_id()));
+        format!(
+            "{}/categories/{}/status/{}",
+            base_url,
+            category_id,
+            purpose.as_str()
+        )
+    }
+    //an entry in the revocation and suspension lists of the category
+    pub(crate) fn internal_credential_status(
+        &self,
+        category_id: &CategoryId,
+        token_id: &TokenId,
+    ) -> Vec<CredentialStatus> {
+        let contract_id = env::current_account_id();
+        if let Some(index) = self.status_index_by_token.get(token_id) {
+            [StatusPurpose::Revocation, StatusPurpose::Suspension]
+                .iter()
+                .map(|purpose| {
+                    let list_id = self.internal_status_list_url(category_id, *purpose);
+                    CredentialStatus {
+                        id: format!("{}#{}", list_id, index),
+                        status_type: STATUS_LIST_ENTRY_TYPE.to_string(),
+                        status_purpose: Some(*purpose),
+                        status_list_index: Some(index.to_string()),
+                        status_list_credential: Some(list_id),
+                        contract_id: contract_id.clone(),
+                        token_id: token_id.clone(),
+                    }
+                })
+                .collect()
+        } else {
+            vec![CredentialStatus {
+                id: format!("{}/certs/{}#status", did_near(&contract_id), token_id),
+                status_type: CERTY_CERT_STATUS_TYPE.to_string(),
+                status_purpose: None,
+                status_list_index: None,
+                status_list_credential: None,
+                contract_id,
+                token_id: token_id.clone(),
+            }]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc3339_from_ms_epoch() {
+        assert_eq!(rfc3339_from_ms(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339_from_ms(999), "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn rfc3339_from_ms_leap_days() {
+        assert_eq!(rfc3339_from_ms(1_709_210_096_000), "2024-02-29T12:34:56Z");
+        //divisible by 400, so a leap year
+        assert_eq!(rfc3339_from_ms(951_782_400_000), "2000-02-29T00:00:00Z");
+        //divisible by 100 only, so the day after February 28th is March 1st
+        assert_eq!(rfc3339_from_ms(4_107_542_399_000), "2100-02-28T23:59:59Z");
+        assert_eq!(rfc3339_from_ms(4_107_542_400_000), "2100-03-01T00:00:00Z");
+    }
+
+    #[test]
+    fn rfc3339_from_ms_far_future() {
+        assert_eq!(rfc3339_from_ms(253_402_300_799_000), "9999-12-31T23:59:59Z");
+    }
+}
@@ -0,0 +1,490 @@
+use crate::*;
+use near_sdk::bs58;
+
+pub const DID_CONTEXT_V1: &str = "https://www.w3.org/ns/did/v1";
+pub const ED25519_2020_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2020/v1";
+pub const CERTY_CATEGORY_SERVICE_TYPE: &str = "CertyCertCategory";
+//fragments of the services listing the categories of an account
+pub const CERTY_CATEGORY_SERVICE_PREFIX: &str = "category-";
+
+//Public key an account publishes in its DID document
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct DidVerificationMethod {
+    pub id: String, // fragment of the method, ex. "key-1"
+    #[serde(rename = "type")]
+    pub method_type: String, // ex. "Ed25519VerificationKey2020"
+    pub public_key_multibase: String, // ex. "z6Mkf5rGMoatrSj1f4CyvuHBeXJELe9RPdzo2PKGNCKVtZxP"
+}
+
+//Endpoint an account publishes in its DID document
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct DidService {
+    pub id: String, // fragment of the service, ex. "website"
+    #[serde(rename = "type")]
+    pub service_type: String, // ex. "LinkedDomains"
+    pub service_endpoint: String, // ex. "https://aws.amazon.com"
+}
+
+//Verification methods and services registered by an account
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct DidRecord {
+    pub verification_methods: Vec<DidVerificationMethod>,
+    pub services: Vec<DidService>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocumentVerificationMethod {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub method_type: String,
+    pub controller: String,
+    pub public_key_multibase: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocumentService {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub service_type: String,
+    pub service_endpoint: String,
+}
+
+//DID document of a did:near identifier
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocument {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    pub verification_method: Vec<DidDocumentVerificationMethod>,
+    pub authentication: Vec<String>,
+    pub assertion_method: Vec<String>,
+    pub service: Vec<DidDocumentService>,
+}
+
+//fragments are what follows the `#` of a DID URL
+pub(crate) fn assert_valid_fragment(id: &str) {
+    assert!(
+        !id.is_empty() && !id.contains('#'),
+        "Id must be a non-empty fragment without '#'"
+    );
+}
+
+//`publicKeyMultibase` values are base58btc, which multibase marks with a leading `z`
+pub(crate) fn assert_valid_multibase(public_key_multibase: &str) {
+    assert!(
+        public_key_multibase.len() > 1
+            && public_key_multibase.starts_with('z')
+            && bs58::decode(&public_key_multibase[1..]).into_vec().is_ok(),
+        "publicKeyMultibase must be base58btc with a 'z' prefix"
+    );
+}
+
+#[near_bindgen]
+impl Contract {
+    //Account adds a public key to its DID document
+    #[payable]
+    pub fn did_verification_method_add(&mut self, verification_method: DidVerificationMethod) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        assert_valid_fragment(&verification_method.id);
+        assert_valid_multibase(&verification_method.public_key_multibase);
+        let account_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+        let mut record = self.did_records.get(&account_id).unwrap_or_default();
+        assert!(
+            !record
+                .verification_methods
+                .iter()
+                .any(|method| method.id == verification_method.id),
+            "Verification method already exists"
+        );
+        //issuer keys share the fragments of the document
+        assert!(
+            !self
+                .issuer_keys
+                .get(&account_id)
+                .unwrap_or_default()
+                .iter()
+                .any(|key| key.key_id == verification_method.id),
+            "Verification method already exists"
+        );
+        let id = verification_method.id.clone();
+        record.verification_methods.push(verification_method);
+        self.did_records.insert(&account_id, &record);
+        let did_verification_method_add_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::DidVerificationMethodAdd(vec![DidUpdateLog {
+                account_id: account_id.to_string(),
+                ids: vec![id],
+            }]),
+        };
+        did_verification_method_add_log.emit();
+        self.internal_storage_settle(&account_id, initial_storage_usage);
+    }
+    #[payable]
+    pub fn did_verification_method_remove(&mut self, id: String) {
+        assert_one_yocto();
+        self.assert_not_paused();
+        let account_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+        let mut record = self.did_records.get(&account_id).unwrap_or_default();
+        let len = record.verification_methods.len();
+        record.verification_methods.retain(|method| method.id != id);
+        assert!(
+            record.verification_methods.len() < len,
+            "No verification method"
+        );
+        self.internal_did_record_save(&account_id, record);
+        let did_verification_method_remove_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::DidVerificationMethodRemove(vec![DidUpdateLog {
+                account_id: account_id.to_string(),
+                ids: vec![id],
+            }]),
+        };
+        did_verification_method_remove_log.emit();
+        self.internal_storage_settle(&account_id, initial_storage_usage);
+    }
+    //Account adds a service endpoint to its DID document
+    #[payable]
+    pub fn did_service_add(&mut self, service: DidService) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        assert_valid_fragment(&service.id);
+        assert!(
+            !service.id.starts_with(CERTY_CATEGORY_SERVICE_PREFIX),
+            "Service ids starting with '{}' are reserved for categories",
+            CERTY_CATEGORY_SERVICE_PREFIX
+        );
+        let account_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+        let mut record = self.did_records.get(&account_id).unwrap_or_default();
+        assert!(
+            !record.services.iter().any(|other| other.id == service.id),
+            "Service already exists"
+        );
+        let id = service.id.clone();
+        record.services.push(service);
+        self.did_records.insert(&account_id, &record);
+        let did_service_add_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::DidServiceAdd(vec![DidUpdateLog {
+                account_id: account_id.to_string(),
+                ids: vec![id],
+            }]),
+        };
+        did_service_add_log.emit();
+        self.internal_storage_settle(&account_id, initial_storage_usage);
+    }
+    #[payable]
+    pub fn did_service_remove(&mut self, id: String) {
+        assert_one_yocto();
+        self.assert_not_paused();
+        let account_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+        let mut record = self.did_records.get(&account_id).unwrap_or_default();
+        let len = record.services.len();
+        record.services.retain(|service| service.id != id);
+        assert!(record.services.len() < len, "No service");
+        self.internal_did_record_save(&account_id, record);
+        let did_service_remove_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::DidServiceRemove(vec![DidUpdateLog {
+                account_id: account_id.to_string(),
+                ids: vec![id],
+            }]),
+        };
+        did_service_remove_log.emit();
+        self.internal_storage_settle(&account_id, initial_storage_usage);
+    }
+    //DID document of `did:near:<account_id>`, the categories the account owns are listed as services
+    //and its active issuer keys as assertion methods
+    pub fn did_document(&self, account_id: AccountId) -> DidDocument {
+        let did = did_near(&account_id);
+        let record = self.did_records.get(&account_id).unwrap_or_default();
+        let mut verification_method: Vec<DidDocumentVerificationMethod> = record
+            .verification_methods
+            .into_iter()
+            .map(|method| DidDocumentVerificationMethod {
+                id: format!("{}#{}", did, method.id),
+                method_type: method.method_type,
+                controller: did.clone(),
+                public_key_multibase: method.public_key_multibase,
+            })
+            .collect();
+        let method_ids: Vec<String> = verification_method
+            .iter()
+            .map(|method| method.id.clone())
+            .collect();
+        //issuer keys currently signing certificates only assert, they do not authenticate
+        let mut assertion_method = method_ids.clone();
+        let now = env::block_timestamp_ms();
+        for key in self.issuer_keys.get(&account_id).unwrap_or_default() {
+            if key.is_active_at(now) {
+                let id = format!("{}#{}", did, key.key_id);
+                assertion_method.push(id.clone());
+                verification_method.push(DidDocumentVerificationMethod {
+                    id,
+                    method_type: ED25519_2020_KEY_TYPE.to_string(),
+                    controller: did.clone(),
+                    public_key_multibase: key.public_key_multibase(),
+                });
+            }
+        }
+        let mut service: Vec<DidDocumentService> = record
+            .services
+            .into_iter()
+            .map(|service| DidDocumentService {
+                id: format!("{}#{}", did, service.id),
+                service_type: service.service_type,
+                service_endpoint: service.service_endpoint,
+            })
+            .collect();
+        if let Some(categories) = self.categories_per_owner.get(&account_id) {
+            let contract_did = did_near(&env::current_account_id());
+            service.extend(categories.iter().map(|category_id| DidDocumentService {
+                id: format!("{}#{}{}", did, CERTY_CATEGORY_SERVICE_PREFIX, category_id),
+                service_type: CERTY_CATEGORY_SERVICE_TYPE.to_string(),
+                service_endpoint: format!("{}/categories/{}", contract_did, category_id),
+            }));
+        }
+
+        DidDocument {
+            context: vec![DID_CONTEXT_V1.to_string(), ED25519_2020_CONTEXT.to_string()],
+            id: did,
+            verification_method,
+            authentication: method_ids,
+            assertion_method,
+            service,
+        }
+    }
+}
+
+impl Contract {
+    //store the record of an account, dropping it once it is empty
+    fn internal_did_record_save(&mut self, account_id: &AccountId, record: DidRecord) {
+        if record.verification_methods.is_empty() && record.services.is_empty() {
+            self.did_records.remove(account_id);
+        } else {
+            self.did_records.insert(account_id, &record);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{context, setup};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::{testing_env, CurveType, PublicKey};
+    use std::convert::TryFrom;
+
+    //ed25519 key made of `byte` repeated
+    fn public_key(byte: u8) -> PublicKey {
+        let mut bytes = vec![CurveType::ED25519 as u8];
+        bytes.extend_from_slice(&[byte; 32]);
+        PublicKey::try_from(bytes).unwrap()
+    }
+
+    //`publicKeyMultibase` of `public_key(byte)`
+    fn multibase(byte: u8) -> String {
+        let mut bytes = ED25519_MULTICODEC.to_vec();
+        bytes.extend_from_slice(&[byte; 32]);
+        format!("z{}", bs58::encode(bytes).into_string())
+    }
+
+    fn verification_method(id: &str) -> DidVerificationMethod {
+        DidVerificationMethod {
+            id: id.to_string(),
+            method_type: ED25519_2020_KEY_TYPE.to_string(),
+            public_key_multibase: multibase(7),
+        }
+    }
+
+    fn service(id: &str) -> DidService {
+        DidService {
+            id: id.to_string(),
+            service_type: "LinkedDomains".to_string(),
+            service_endpoint: "https://certy.example".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_did_verification_method_add_remove() {
+        let mut contract = setup();
+        contract.did_verification_method_add(verification_method("key-1"));
+        contract.did_verification_method_add(verification_method("key-2"));
+        let document = contract.did_document(accounts(1));
+        let did = format!("did:near:{}", accounts(1));
+        assert_eq!(
+            document.authentication,
+            vec![format!("{}#key-1", did), format!("{}#key-2", did)]
+        );
+        assert_eq!(document.assertion_method, document.authentication);
+        assert_eq!(document.verification_method[0].controller, did);
+
+        testing_env!(context(1, 1).build());
+        contract.did_verification_method_remove("key-1".to_string());
+        let document = contract.did_document(accounts(1));
+        assert_eq!(document.authentication, vec![format!("{}#key-2", did)]);
+        contract.did_verification_method_remove("key-2".to_string());
+        assert!(contract.did_records.get(&accounts(1)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Verification method already exists")]
+    fn test_did_verification_method_add_duplicate() {
+        let mut contract = setup();
+        contract.did_verification_method_add(verification_method("key-1"));
+        contract.did_verification_method_add(verification_method("key-1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Verification method already exists")]
+    fn test_did_verification_method_add_issuer_key_clash() {
+        let mut contract = setup();
+        contract.issuer_key_add("key-1".to_string(), public_key(7), None, None);
+        contract.did_verification_method_add(verification_method("key-1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Id must be a non-empty fragment without '#'")]
+    fn test_did_verification_method_add_invalid_fragment() {
+        let mut contract = setup();
+        contract.did_verification_method_add(verification_method("did:near:alice#key-1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "publicKeyMultibase must be base58btc with a 'z' prefix")]
+    fn test_did_verification_method_add_missing_multibase_prefix() {
+        let mut contract = setup();
+        let mut method = verification_method("key-1");
+        method.public_key_multibase = bs58::encode([7u8; 32]).into_string();
+        contract.did_verification_method_add(method);
+    }
+
+    #[test]
+    #[should_panic(expected = "publicKeyMultibase must be base58btc with a 'z' prefix")]
+    fn test_did_verification_method_add_invalid_base58() {
+        let mut contract = setup();
+        let mut method = verification_method("key-1");
+        //'0' and 'l' are not in the base58btc alphabet
+        method.public_key_multibase = "z0l".to_string();
+        contract.did_verification_method_add(method);
+    }
+
+    #[test]
+    #[should_panic(expected = "No verification method")]
+    fn test_did_verification_method_remove_unknown() {
+        let mut contract = setup();
+        testing_env!(context(1, 1).build());
+        contract.did_verification_method_remove("key-1".to_string());
+    }
+
+    #[test]
+    fn test_did_service_add_remove() {
+        let mut contract = setup();
+        contract.did_service_add(service("website"));
+        let did = format!("did:near:{}", accounts(1));
+        let document = contract.did_document(accounts(1));
+        assert_eq!(document.service[0].id, format!("{}#website", did));
+        assert_eq!(
+            document.service[0].service_endpoint,
+            "https://certy.example"
+        );
+
+        testing_env!(context(1, 1).build());
+        contract.did_service_remove("website".to_string());
+        let document = contract.did_document(accounts(1));
+        assert!(document
+            .service
+            .iter()
+            .all(|service| service.service_type == CERTY_CATEGORY_SERVICE_TYPE));
+        assert!(contract.did_records.get(&accounts(1)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Service already exists")]
+    fn test_did_service_add_duplicate() {
+        let mut contract = setup();
+        contract.did_service_add(service("website"));
+        contract.did_service_add(service("website"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Id must be a non-empty fragment without '#'")]
+    fn test_did_service_add_empty_fragment() {
+        let mut contract = setup();
+        contract.did_service_add(service(""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Service ids starting with 'category-' are reserved for categories")]
+    fn test_did_service_add_reserved_prefix() {
+        let mut contract = setup();
+        contract.did_service_add(service("category-1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "No service")]
+    fn test_did_service_remove_unknown() {
+        let mut contract = setup();
+        testing_env!(context(1, 1).build());
+        contract.did_service_remove("website".to_string());
+    }
+
+    #[test]
+    fn test_did_document() {
+        let mut contract = setup();
+        contract.did_verification_method_add(verification_method("key-1"));
+        contract.did_service_add(service("website"));
+        contract.issuer_key_add("pki".to_string(), public_key(9), None, None);
+
+        let did = format!("did:near:{}", accounts(1));
+        let document = contract.did_document(accounts(1));
+        assert_eq!(document.id, did);
+        assert_eq!(
+            document.context,
+            vec![DID_CONTEXT_V1.to_string(), ED25519_2020_CONTEXT.to_string()]
+        );
+        assert_eq!(document.authentication, vec![format!("{}#key-1", did)]);
+        assert_eq!(
+            document.assertion_method,
+            vec![format!("{}#key-1", did), format!("{}#pki", did)]
+        );
+        let issuer_key = &document.verification_method[1];
+        assert_eq!(issuer_key.id, format!("{}#pki", did));
+        assert_eq!(issuer_key.method_type, ED25519_2020_KEY_TYPE);
+        assert_eq!(issuer_key.controller, did);
+        assert_eq!(issuer_key.public_key_multibase, multibase(9));
+        assert_eq!(document.service.len(), 2);
+        assert_eq!(document.service[0].id, format!("{}#website", did));
+        let category = &document.service[1];
+        assert_eq!(category.id, format!("{}#category-1", did));
+        assert_eq!(category.service_type, CERTY_CATEGORY_SERVICE_TYPE);
+        assert_eq!(
+            category.service_endpoint,
+            format!("did:near:{}/categories/1", accounts(0))
+        );
+
+        //an account with nothing registered still resolves to an empty document
+        let document = contract.did_document(accounts(3));
+        assert_eq!(document.id, format!("did:near:{}", accounts(3)));
+        assert!(document.verification_method.is_empty());
+        assert!(document.service.is_empty());
+    }
+}
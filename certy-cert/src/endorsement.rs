@@ -0,0 +1,172 @@
+use crate::*;
+
+//An endorsement attached to a certificate by a third party
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Endorsement {
+    pub comment: String,
+    pub reference: Option<String>, // URL to an off-chain JSON file with more info.
+    pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
+    pub endorsed_at: u64, // When endorsement was made, Unix epoch in milliseconds
+    pub hidden: bool,     // Whether the holder hid the endorsement from their certificate
+}
+
+//The Json endorsement is what will be returned from view calls.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonEndorsement {
+    pub token_id: TokenId,
+    pub endorser_id: AccountId,
+    pub comment: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_hash: Option<Base64VecU8>,
+    pub endorsed_at: u64,
+    pub hidden: bool,
+}
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn endorsement_add(
+        &mut self,
+        token_id: TokenId,
+        comment: String,
+        reference: Option<String>,
+        reference_hash: Option<Base64VecU8>,
+    ) {
+        assert_at_least_one_yocto();
+        let endorser_id = env::predecessor_account_id();
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+        assert_ne!(
+            endorser_id, token.owner_id,
+            "Holder can't endorse own certificate"
+        );
+        let initial_storage_usage = env::storage_usage();
+        //replacing an endorsement keeps the holder's choice to hide it
+        let hidden = self
+            .endorsements_per_token
+            .get(&token_id)
+            .and_then(|endorsements| endorsements.get(&endorser_id))
+            .map(|endorsement| endorsement.hidden)
+            .unwrap_or(false);
+        self.internal_endorsement_add(
+            &token_id,
+            &endorser_id,
+            &Endorsement {
+                comment: comment.clone(),
+                reference: reference.clone(),
+                reference_hash: reference_hash.clone(),
+                endorsed_at: env::block_timestamp_ms(),
+                hidden,
+            },
+        );
+        let endorsement_add_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::EndorsementAdd(vec![EndorsementAddLog {
+                endorser_id: endorser_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+                comment,
+                reference,
+                reference_hash,
+            }]),
+        };
+        endorsement_add_log.emit();
+        self.internal_storage_settle(&endorser_id, initial_storage_usage);
+    }
+    #[payable]
+    pub fn endorsement_withdraw(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        let endorser_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+        assert!(
+            self.internal_endorsement_remove(&token_id, &endorser_id),
+            "No endorsement"
+        );
+        let endorsement_withdraw_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::EndorsementWithdraw(vec![EndorsementWithdrawLog {
+                endorser_id: endorser_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+            }]),
+        };
+        endorsement_withdraw_log.emit();
+        self.internal_storage_settle(&endorser_id, initial_storage_usage);
+    }
+    //Holder hides or shows an endorsement on their certificate
+    #[payable]
+    pub fn endorsement_set_hidden(
+        &mut self,
+        token_id: TokenId,
+        endorser_id: AccountId,
+        hidden: bool,
+    ) {
+        assert_one_yocto();
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+        assert_eq!(
+            env::predecessor_account_id(),
+            token.owner_id,
+            "Not cert holder"
+        );
+        let mut endorsements = self
+            .endorsements_per_token
+            .get(&token_id)
+            .expect("No endorsement");
+        let mut endorsement = endorsements.get(&endorser_id).expect("No endorsement");
+        endorsement.hidden = hidden;
+        endorsements.insert(&endorser_id, &endorsement);
+        let endorsement_visibility_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::EndorsementVisibility(vec![EndorsementVisibilityLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                endorser_id: endorser_id.to_string(),
+                token_ids: vec![token_id.to_string()],
+                hidden,
+            }]),
+        };
+        endorsement_visibility_log.emit();
+    }
+    //Query for the endorsements of a certificate, hidden ones are only included on demand
+    pub fn endorsements_for_token(
+        &self,
+        token_id: TokenId,
+        include_hidden: Option<bool>,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonEndorsement> {
+        let endorsements_for_token = self.endorsements_per_token.get(&token_id);
+        let endorsements = if let Some(endorsements_for_token) = endorsements_for_token {
+            endorsements_for_token
+        } else {
+            return vec![];
+        };
+
+        let include_hidden = include_hidden.unwrap_or(false);
+
+        //hidden endorsements are dropped from the page, so a page can hold fewer than `limit`
+        paginate_map(&endorsements, from_index, limit)
+            .items
+            .into_iter()
+            .filter(|(_, endorsement)| include_hidden || !endorsement.hidden)
+            .map(|(endorser_id, endorsement)| JsonEndorsement {
+                token_id: token_id.clone(),
+                endorser_id,
+                comment: endorsement.comment,
+                reference: endorsement.reference,
+                reference_hash: endorsement.reference_hash,
+                endorsed_at: endorsement.endorsed_at,
+                hidden: endorsement.hidden,
+            })
+            .collect()
+    }
+    pub fn endorsement_supply_for_token(&self, token_id: TokenId) -> U128 {
+        if let Some(endorsements_for_token) = self.endorsements_per_token.get(&token_id) {
+            U128(endorsements_for_token.len() as u128)
+        } else {
+            U128(0)
+        }
+    }
+}
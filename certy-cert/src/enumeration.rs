@@ -7,14 +7,16 @@ impl Contract {
     }
 
     pub fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonToken> {
-        let start = u128::from(from_index.unwrap_or(U128(0)));
+        self.nft_tokens_page(from_index, limit).items
+    }
 
-        self.token_metadata_by_id
-            .keys()
-            .skip(start as usize)
-            .take(limit.unwrap_or(50) as usize)
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            .collect()
+    pub fn nft_tokens_page(&self, from_index: Option<U128>, limit: Option<u64>) -> Page<JsonToken> {
+        paginate(
+            self.token_metadata_by_id.keys_as_vector(),
+            from_index,
+            limit,
+        )
+        .map(|token_id| self.nft_token(token_id).unwrap())
     }
 
     pub fn nft_supply_for_owner(&self, account_id: AccountId) -> U128 {
@@ -27,27 +29,208 @@ impl Contract {
         }
     }
 
-    //Query for all the tokens for an owner
+    //Query for all the tokens for an owner, only the ones with the given status if any
     pub fn nft_tokens_for_owner(
         &self,
         account_id: AccountId,
         from_index: Option<U128>,
         limit: Option<u64>,
+        status: Option<CertStatus>,
     ) -> Vec<JsonToken> {
+        self.nft_tokens_for_owner_page(account_id, from_index, limit, status)
+            .items
+    }
+
+    //a page filtered by status can hold less than `limit` tokens, the cursor still moves by `limit`
+    pub fn nft_tokens_for_owner_page(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        status: Option<CertStatus>,
+    ) -> Page<JsonToken> {
         let tokens_for_owner_set = self.tokens_per_owner.get(&account_id);
         let tokens = if let Some(tokens_for_owner_set) = tokens_for_owner_set {
             tokens_for_owner_set
         } else {
-            return vec![];
+            return Page::empty();
+        };
+
+        let now_ms = env::block_timestamp_ms();
+        let mut page = paginate(tokens.as_vector(), from_index, limit)
+            .map(|token_id| self.nft_token(token_id).unwrap());
+        if let Some(status) = status {
+            page.items.retain(|token| {
+                self.internal_cert_status(&token.token_id, &token.metadata, now_ms) == status
+            });
+        }
+        page
+    }
+
+    //Query for all the tokens an owner holds from a category
+    pub fn certs_for_owner_in_category(
+        &self,
+        account_id: AccountId,
+        category_id: CategoryId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonToken> {
+        self.certs_for_owner_in_category_page(account_id, category_id, from_index, limit)
+            .items
+    }
+
+    pub fn certs_for_owner_in_category_page(
+        &self,
+        account_id: AccountId,
+        category_id: CategoryId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Page<JsonToken> {
+        let tokens_set = self
+            .tokens_per_owner_category
+            .get(&(account_id, category_id));
+        let tokens = if let Some(tokens_set) = tokens_set {
+            tokens_set
+        } else {
+            return Page::empty();
+        };
+
+        paginate(tokens.as_vector(), from_index, limit)
+            .map(|token_id| self.nft_token(token_id).unwrap())
+    }
+
+    //Whether an account holds a certificate from a category, an active one if `active_only`
+    pub fn holds_cert(
+        &self,
+        account_id: AccountId,
+        category_id: CategoryId,
+        active_only: Option<bool>,
+    ) -> bool {
+        if !active_only.unwrap_or(false) {
+            return self
+                .tokens_per_owner_category
+                .contains_key(&(account_id, category_id));
+        }
+        self.internal_valid_cert_of(account_id, category_id, env::block_timestamp_ms())
+            .is_some()
+    }
+
+    pub fn category_total_supply(&self) -> U128 {
+        U128(self.category_metadata_by_id.len() as u128)
+    }
+
+    //Query for all the categories, every created category is kept in `category_metadata_by_id`
+    pub fn categories(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonCategory> {
+        self.categories_page(from_index, limit).items
+    }
+
+    pub fn categories_page(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Page<JsonCategory> {
+        paginate(
+            self.category_metadata_by_id.keys_as_vector(),
+            from_index,
+            limit,
+        )
+        .map(|category_id| self.category_info(category_id).unwrap())
+    }
+
+    pub fn category_supply_for_owner(&self, account_id: AccountId) -> U128 {
+        self.categories_per_owner
+            .get(&account_id)
+            .map(|categories_set| U128(categories_set.len() as u128))
+            .unwrap_or(U128(0))
+    }
+
+    pub fn cert_supply_for_category(&self, category_id: CategoryId) -> U128 {
+        self.tokens_per_category
+            .get(&category_id)
+            .map(|tokens_set| U128(tokens_set.len() as u128))
+            .unwrap_or(U128(0))
+    }
+
+    //Query for the certificates whose `media_hash` or `reference_hash` is the sha256 hash of a document,
+    //with their status so a revoked or suspended match isn't taken for a valid one.
+    //Burned certificates are not listed
+    pub fn cert_by_document_hash(
+        &self,
+        hash: Base64VecU8,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Page<JsonTokenWithStatus> {
+        let tokens_set = if let Some(tokens_set) = self.tokens_per_document_hash.get(&hash.0) {
+            tokens_set
+        } else {
+            return Page::empty();
         };
 
-        let start = u128::from(from_index.unwrap_or(U128(0)));
+        paginate(tokens_set.as_vector(), from_index, limit).map(|token_id| JsonTokenWithStatus {
+            status: self.cert_status(token_id.clone()).unwrap(),
+            token: self.nft_token(token_id).unwrap(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{category_metadata, context, token_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    fn metadata(media_hash: Option<Base64VecU8>) -> TokenMetadata {
+        TokenMetadata {
+            media_hash,
+            ..token_metadata()
+        }
+    }
+
+    //category "1" of accounts(1) with certificates "0" and "1" of the same document minted to accounts(2)
+    fn setup(hash: &Base64VecU8) -> Contract {
+        testing_env!(context(1, 10u128.pow(24)).build());
+        let mut contract = Contract::new_default_meta(accounts(0));
+        contract.category_create("1".to_string(), category_metadata());
+        for _ in 0..2 {
+            contract.nft_mint(metadata(Some(hash.clone())), accounts(2), "1".to_string());
+        }
+        contract
+    }
+
+    #[test]
+    fn cert_by_document_hash_pages_matches() {
+        let hash = Base64VecU8(env::sha256(b"certificate.pdf"));
+        let mut contract = setup(&hash);
+
+        let page = contract.cert_by_document_hash(hash.clone(), None, Some(1));
+        assert_eq!(page.items.len(), 1);
+        let next_cursor = page.next_cursor.unwrap();
+        let page = contract.cert_by_document_hash(hash.clone(), Some(next_cursor), Some(1));
+        assert_eq!(page.items.len(), 1);
+        assert!(page.next_cursor.is_none());
+
+        testing_env!(context(1, 1).build());
+        contract.cert_delete("0".to_string());
+        let page = contract.cert_by_document_hash(hash.clone(), None, None);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].token.token_id, "1".to_string());
+        assert_eq!(page.items[0].status, CertStatus::Active);
+    }
+
+    #[test]
+    fn cert_by_document_hash_follows_update() {
+        let hash = Base64VecU8(env::sha256(b"certificate.pdf"));
+        let mut contract = setup(&hash);
+        let other_hash = Base64VecU8(env::sha256(b"other.pdf"));
+        contract.cert_update(metadata(Some(other_hash.clone())), "0".to_string(), None);
 
-        tokens
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(50) as usize)
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            .collect()
+        let page = contract.cert_by_document_hash(other_hash, None, None);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].token.token_id, "0".to_string());
+        assert_eq!(
+            contract.cert_by_document_hash(hash, None, None).items.len(),
+            1
+        );
     }
 }
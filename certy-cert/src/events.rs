@@ -4,7 +4,12 @@ use near_sdk::{
     serde_json,
 };
 
-use crate::{CategoryMetadata, TokenMetadata, CategoryId};
+use near_sdk::json_types::{Base64VecU8, U128};
+
+use crate::{
+    Accreditation, CategoryId, CategoryMetadata, IssuerVerification, JsonCategoryPrice,
+    JsonFeeConfig, NFTContractMetadata, TokenMetadata,
+};
 
 /// Enum that represents the data type of the EventLog.
 #[derive(Serialize, Deserialize, Debug)]
@@ -19,6 +24,51 @@ pub enum EventLogVariant {
     CategoryUpdate(Vec<CategoryUpdateLog>),
     CategoryDelete(Vec<CategoryDeleteLog>),
     NftUpdate(Vec<NftUpdateLog>),
+    EndorsementAdd(Vec<EndorsementAddLog>),
+    EndorsementWithdraw(Vec<EndorsementWithdrawLog>),
+    EndorsementVisibility(Vec<EndorsementVisibilityLog>),
+    VerifierAdd(Vec<VerifierLog>),
+    VerifierRemove(Vec<VerifierLog>),
+    IssuerVerify(Vec<IssuerVerifyLog>),
+    IssuerVerificationRevoke(Vec<IssuerVerificationRevokeLog>),
+    RootAccreditorAdd(Vec<RootAccreditorLog>),
+    RootAccreditorRemove(Vec<RootAccreditorLog>),
+    AccreditationGrant(Vec<AccreditationGrantLog>),
+    AccreditationRevoke(Vec<AccreditationRevokeLog>),
+    OwnerTransferPropose(Vec<OwnerTransferLog>),
+    OwnerTransferAccept(Vec<OwnerTransferLog>),
+    AdminAdd(Vec<AdminLog>),
+    AdminRemove(Vec<AdminLog>),
+    ContractMetadataUpdate(Vec<ContractMetadataUpdateLog>),
+    ContractPause(Vec<ContractPauseLog>),
+    ContractUnpause(Vec<ContractPauseLog>),
+    CategoryFreeze(Vec<CategoryFreezeLog>),
+    CategoryUnfreeze(Vec<CategoryFreezeLog>),
+    SponsorDeposit(Vec<SponsorLog>),
+    SponsorWithdraw(Vec<SponsorLog>),
+    FeeConfigUpdate(Vec<FeeConfigUpdateLog>),
+    FeeCollect(Vec<FeeCollectLog>),
+    FeeWithdraw(Vec<FeeWithdrawLog>),
+    FtWhitelistAdd(Vec<FtWhitelistLog>),
+    FtWhitelistRemove(Vec<FtWhitelistLog>),
+    CategoryPriceUpdate(Vec<CategoryPriceUpdateLog>),
+    CertClaim(Vec<CertClaimLog>),
+    CertRevoke(Vec<CertRevokeLog>),
+    CertSuspend(Vec<CertSuspendLog>),
+    CertResume(Vec<CertResumeLog>),
+    CategoryArbiterUpdate(Vec<CategoryArbiterUpdateLog>),
+    AppealOpen(Vec<AppealOpenLog>),
+    AppealRespond(Vec<AppealRespondLog>),
+    AppealDecide(Vec<AppealDecideLog>),
+    DidVerificationMethodAdd(Vec<DidUpdateLog>),
+    DidVerificationMethodRemove(Vec<DidUpdateLog>),
+    DidServiceAdd(Vec<DidUpdateLog>),
+    DidServiceRemove(Vec<DidUpdateLog>),
+    IssuerKeyAdd(Vec<IssuerKeyLog>),
+    IssuerKeyRevoke(Vec<IssuerKeyLog>),
+    FtPayoutHold(Vec<FtPayoutLog>),
+    FtPayoutWithdraw(Vec<FtPayoutLog>),
+    CredentialBaseUrlUpdate(Vec<CredentialBaseUrlUpdateLog>),
 
 }
 
@@ -80,7 +130,8 @@ pub struct NftMintLog {
 ///
 /// Arguments
 /// * `authorized_id`: the account called the method
-/// * `category_ids`: ["1", "12345abc"]
+/// * `token_ids`: ["1", "12345abc"]
+/// * `reason`: optional reason given by the issuer
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct NftUpdateLog {
@@ -88,6 +139,8 @@ pub struct NftUpdateLog {
     pub token_ids: Vec<String>,
     pub old_token_metadatas: Vec<TokenMetadata>,
     pub new_token_metadatas: Vec<TokenMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 /// An event log to capture token transfer
@@ -152,3 +205,487 @@ pub struct CategoryDeleteLog {
     pub authorized_id: Option<String>,
     pub category_ids: Vec<String>,
 }
+
+/// An event log to capture endorsement add
+///
+/// Arguments
+/// * `endorser_id`: the account endorsing the certificate
+/// * `token_ids`: ["1", "12345abc"]
+/// * `comment`: the endorsement text
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EndorsementAddLog {
+    pub endorser_id: String,
+    pub token_ids: Vec<String>,
+    pub comment: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_hash: Option<Base64VecU8>,
+}
+
+/// An event log to capture endorsement withdraw
+///
+/// Arguments
+/// * `endorser_id`: the account that endorsed the certificate
+/// * `token_ids`: ["1", "12345abc"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EndorsementWithdrawLog {
+    pub endorser_id: String,
+    pub token_ids: Vec<String>,
+}
+
+/// An event log to capture the holder hiding or showing an endorsement
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `endorser_id`: the account that endorsed the certificate
+/// * `token_ids`: ["1", "12345abc"]
+/// * `hidden`: whether the endorsement is now hidden
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EndorsementVisibilityLog {
+    pub authorized_id: Option<String>,
+    pub endorser_id: String,
+    pub token_ids: Vec<String>,
+    pub hidden: bool,
+}
+
+/// An event log to capture verifier add or remove
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `account_ids`: ["verifier.near"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VerifierLog {
+    pub authorized_id: Option<String>,
+    pub account_ids: Vec<String>,
+}
+
+/// An event log to capture issuer verification
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `account_id`: the verified category owner
+/// * `verification`: display name, domain and evidence of the institution
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IssuerVerifyLog {
+    pub authorized_id: Option<String>,
+    pub account_id: String,
+    pub verification: IssuerVerification,
+}
+
+/// An event log to capture issuer verification revoke
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `account_ids`: ["issuer.near"]
+/// * `reason`: optional reason of the revocation
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IssuerVerificationRevokeLog {
+    pub authorized_id: Option<String>,
+    pub account_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An event log to capture root accreditor add or remove
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `account_ids`: ["ministry.near"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RootAccreditorLog {
+    pub authorized_id: Option<String>,
+    pub account_ids: Vec<String>,
+}
+
+/// An event log to capture accreditation grant
+///
+/// Arguments
+/// * `account_id`: the accredited account
+/// * `accreditation`: accreditor, scope and validity of the accreditation
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccreditationGrantLog {
+    pub account_id: String,
+    pub accreditation: Accreditation,
+}
+
+/// An event log to capture accreditation revoke
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `accreditor_id`: the account that granted the accreditation
+/// * `account_ids`: ["university.near"]
+/// * `reason`: optional reason of the revocation
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccreditationRevokeLog {
+    pub authorized_id: Option<String>,
+    pub accreditor_id: String,
+    pub account_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An event log to capture contract owner transfer propose or accept
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "new_owner.near"
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnerTransferLog {
+    pub authorized_id: Option<String>,
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+}
+
+/// An event log to capture admin add or remove
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `account_ids`: ["admin.near"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdminLog {
+    pub authorized_id: Option<String>,
+    pub account_ids: Vec<String>,
+}
+
+/// An event log to capture contract metadata update
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `old_metadata`: contract metadata before the update
+/// * `new_metadata`: contract metadata after the update
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractMetadataUpdateLog {
+    pub authorized_id: Option<String>,
+    pub old_metadata: NFTContractMetadata,
+    pub new_metadata: NFTContractMetadata,
+}
+
+/// An event log to capture contract pause or unpause
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractPauseLog {
+    pub authorized_id: Option<String>,
+}
+
+/// An event log to capture category freeze or unfreeze
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `category_ids`: ["1", "12345abc"]
+/// * `reason`: optional reason of the freeze
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CategoryFreezeLog {
+    pub authorized_id: Option<String>,
+    pub category_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An event log to capture sponsorship deposit or withdraw
+///
+/// Arguments
+/// * `sponsor_id`: the account paying the storage
+/// * `beneficiary_id`: the account whose storage is paid
+/// * `amount`: yoctoNEAR added to or taken from the sponsorship
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SponsorLog {
+    pub sponsor_id: String,
+    pub beneficiary_id: String,
+    pub amount: U128,
+}
+
+/// An event log to capture protocol fee config update
+///
+/// Arguments
+/// * `authorized_id`: approved account to update the fees
+/// * `old_fee_config`: fees before the update
+/// * `new_fee_config`: fees after the update
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeConfigUpdateLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub old_fee_config: JsonFeeConfig,
+    pub new_fee_config: JsonFeeConfig,
+}
+
+/// An event log to capture protocol fee collected on issuance
+///
+/// Arguments
+/// * `account_id`: the account paying the fee
+/// * `amount`: yoctoNEAR collected
+/// * `token_ids`: ["1", "2"] minted certs, if the fee is for minting
+/// * `category_ids`: ["1", "2"] created categories, if the fee is for category creation
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeCollectLog {
+    pub account_id: String,
+    pub amount: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_ids: Option<Vec<String>>,
+}
+
+/// An event log to capture protocol fee withdraw
+///
+/// Arguments
+/// * `authorized_id`: approved account to withdraw the fees
+/// * `receiver_id`: the account receiving the fees
+/// * `amount`: yoctoNEAR withdrawn
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeeWithdrawLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub receiver_id: String,
+    pub amount: U128,
+}
+
+/// An event log to capture NEP-141 token whitelisting for claim prices
+///
+/// Arguments
+/// * `authorized_id`: approved account to manage the whitelist
+/// * `ft_contract_ids`: ["usdc.testnet"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtWhitelistLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub ft_contract_ids: Vec<String>,
+}
+
+/// An event log to capture category claim price update
+///
+/// Arguments
+/// * `authorized_id`: approved account to set the price
+/// * `category_id`: "1"
+/// * `price`: the new price, none if claims are disabled
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CategoryPriceUpdateLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub category_id: String,
+    pub price: Option<JsonCategoryPrice>,
+}
+
+/// An event log to capture paid certificate claim
+///
+/// Arguments
+/// * `owner_id`: the account paying and receiving the cert
+/// * `category_id`: "1"
+/// * `token_ids`: ["1"]
+/// * `ft_contract_id`: token the price is paid in, NEAR if none
+/// * `amount`: price paid
+/// * `fee`: part of the price kept as platform fee
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CertClaimLog {
+    pub owner_id: String,
+    pub category_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ft_contract_id: Option<String>,
+    pub amount: U128,
+    pub fee: U128,
+}
+
+/// An event log to capture certificate revocation
+///
+/// Arguments
+/// * `authorized_id`: approved account to revoke
+/// * `token_ids`: ["1"]
+/// * `reason`: optional reason of the revocation
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CertRevokeLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub token_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An event log to capture certificate suspension
+///
+/// Arguments
+/// * `authorized_id`: approved account to suspend
+/// * `token_ids`: ["1"]
+/// * `reason`: optional reason of the suspension
+/// * `resume_at`: when the suspension lifts by itself, Unix epoch in milliseconds
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CertSuspendLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub token_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resume_at: Option<u64>,
+}
+
+/// An event log to capture certificate resume
+///
+/// Arguments
+/// * `authorized_id`: the account called the method
+/// * `token_ids`: ["1"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CertResumeLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub token_ids: Vec<String>,
+}
+
+/// An event log to capture category arbiter update
+///
+/// Arguments
+/// * `authorized_id`: approved account to set the arbiter
+/// * `category_id`: "1"
+/// * `arbiter_id`: the new arbiter, none if removed
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CategoryArbiterUpdateLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub category_id: String,
+    pub arbiter_id: Option<String>,
+}
+
+/// An event log to capture appeal open
+///
+/// Arguments
+/// * `owner_id`: the holder appealing the revocation
+/// * `token_ids`: ["1"]
+/// * `evidence_reference`: URL to the evidence
+/// * `evidence_hash`: optional hash of the evidence
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppealOpenLog {
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+    pub evidence_reference: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence_hash: Option<Base64VecU8>,
+}
+
+/// An event log to capture appeal response
+///
+/// Arguments
+/// * `authorized_id`: approved account to respond
+/// * `token_ids`: ["1"]
+/// * `message`: the response
+/// * `reference`: optional URL backing the response
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppealRespondLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub token_ids: Vec<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+}
+
+/// An event log to capture appeal decision
+///
+/// Arguments
+/// * `authorized_id`: category owner or arbiter
+/// * `token_ids`: ["1"]
+/// * `granted`: whether the revocation was lifted
+/// * `reason`: optional reason of the decision
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppealDecideLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub token_ids: Vec<String>,
+    pub granted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// An event log to capture verification methods or services added to or removed from a DID document
+///
+/// Arguments
+/// * `account_id`: the account owning the DID document
+/// * `ids`: ["key-1"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DidUpdateLog {
+    pub account_id: String,
+    pub ids: Vec<String>,
+}
+
+/// An event log to capture ed25519 keys registered or revoked by an issuer
+///
+/// Arguments
+/// * `account_id`: the issuer owning the keys
+/// * `key_ids`: ["pki-2023"]
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IssuerKeyLog {
+    pub account_id: String,
+    pub key_ids: Vec<String>,
+}
+
+/// An event log to capture NEP-141 payouts held by the contract or withdrawn from it
+///
+/// Arguments
+/// * `account_id`: the account the payout belongs to
+/// * `ft_contract_id`: "usdc.testnet"
+/// * `amount`: payout held or withdrawn
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FtPayoutLog {
+    pub account_id: String,
+    pub ft_contract_id: String,
+    pub amount: U128,
+}
+
+/// An event log to capture the update of the URL status lists are served under
+///
+/// Arguments
+/// * `authorized_id`: approved account to set the URL
+/// * `base_url`: "https://status.certy.network", none for the did:near path
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CredentialBaseUrlUpdateLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub base_url: Option<String>,
+}
@@ -0,0 +1,100 @@
+use crate::*;
+
+//Protocol fees in yoctoNEAR, collected on top of storage
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct FeeConfig {
+    //fee for every certificate minted
+    pub mint_fee: Balance,
+    //fee for every category created
+    pub category_create_fee: Balance,
+    //share of every paid claim, in basis points of the price
+    pub claim_fee_bps: u16,
+}
+
+//The Json fee config is what will be returned from view calls.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonFeeConfig {
+    pub mint_fee: U128,
+    pub category_create_fee: U128,
+    pub claim_fee_bps: u16,
+}
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn fee_config_set(&mut self, fee_config: JsonFeeConfig) {
+        assert_one_yocto();
+        self.assert_owner();
+        assert!(
+            fee_config.claim_fee_bps <= 10_000,
+            "Claim fee can't be more than 10000 bps"
+        );
+        let old_fee_config = self.fee_config();
+        self.fee_config = FeeConfig {
+            mint_fee: fee_config.mint_fee.0,
+            category_create_fee: fee_config.category_create_fee.0,
+            claim_fee_bps: fee_config.claim_fee_bps,
+        };
+        let fee_config_update_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::FeeConfigUpdate(vec![FeeConfigUpdateLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                old_fee_config,
+                new_fee_config: fee_config,
+            }]),
+        };
+        fee_config_update_log.emit();
+    }
+    //Contract owner takes the collected fees, all of them if no amount is given
+    #[payable]
+    pub fn fees_withdraw(&mut self, amount: Option<U128>) -> U128 {
+        assert_one_yocto();
+        self.assert_owner();
+        let amount = amount.map(|amount| amount.0).unwrap_or(self.fees_collected);
+        assert!(
+            amount <= self.fees_collected,
+            "Can't withdraw more than the collected fees"
+        );
+        self.fees_collected -= amount;
+        if amount > 0 {
+            Promise::new(self.owner_id.clone()).transfer(amount);
+        }
+        let fee_withdraw_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::FeeWithdraw(vec![FeeWithdrawLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                receiver_id: self.owner_id.to_string(),
+                amount: U128(amount),
+            }]),
+        };
+        fee_withdraw_log.emit();
+        U128(self.fees_collected)
+    }
+    pub fn fee_config(&self) -> JsonFeeConfig {
+        JsonFeeConfig {
+            mint_fee: U128(self.fee_config.mint_fee),
+            category_create_fee: U128(self.fee_config.category_create_fee),
+            claim_fee_bps: self.fee_config.claim_fee_bps,
+        }
+    }
+    pub fn fees_collected(&self) -> U128 {
+        U128(self.fees_collected)
+    }
+    //Fee an account pays for minting a certificate, verified issuers are exempt
+    pub fn mint_fee_for(&self, account_id: AccountId) -> U128 {
+        if self.verified_issuers.get(&account_id).is_some() {
+            return U128(0);
+        }
+        U128(self.fee_config.mint_fee)
+    }
+    //Fee an account pays for creating a category, verified issuers are exempt
+    pub fn category_create_fee_for(&self, account_id: AccountId) -> U128 {
+        if self.verified_issuers.get(&account_id).is_some() {
+            return U128(0);
+        }
+        U128(self.fee_config.category_create_fee)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::*;
+use std::ops::Bound;
+
+//key of the issuance feeds: the `issued_at` of a certificate, then its token ID
+pub type IssuanceKey = (u64, TokenId);
+
+//Position in an issuance feed, the key of the last certificate of a page
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FeedCursor {
+    pub issued_at: u64,
+    pub token_id: TokenId,
+}
+
+#[near_bindgen]
+impl Contract {
+    //Query for the most recently issued certificates, of a category if given, newest first.
+    //Pass the `next_cursor` of a page as `before` to get the next one
+    pub fn certs_issued_latest(
+        &self,
+        category_id: Option<CategoryId>,
+        before: Option<FeedCursor>,
+        limit: Option<u64>,
+    ) -> Page<JsonToken, FeedCursor> {
+        let category_feed;
+        let feed = if let Some(category_id) = category_id {
+            if let Some(feed) = self.certs_by_issued_at_per_category.get(&category_id) {
+                category_feed = feed;
+                &category_feed
+            } else {
+                return Page::empty();
+            }
+        } else {
+            &self.certs_by_issued_at
+        };
+        let keys: Vec<IssuanceKey> = if let Some(before) = before {
+            feed.iter_rev_from((before.issued_at, before.token_id))
+                .take(limit.unwrap_or(50) as usize + 1)
+                .map(|(key, _)| key)
+                .collect()
+        } else {
+            feed.iter_rev()
+                .take(limit.unwrap_or(50) as usize + 1)
+                .map(|(key, _)| key)
+                .collect()
+        };
+        self.internal_feed_page(keys, limit.unwrap_or(50))
+    }
+    //Query for the certificates issued from `from_ms` (inclusive) to `to_ms` (exclusive),
+    //of a category if given, oldest first. Pass the `next_cursor` of a page as `after` to get the next one
+    pub fn certs_issued_between(
+        &self,
+        category_id: Option<CategoryId>,
+        from_ms: u64,
+        to_ms: u64,
+        after: Option<FeedCursor>,
+        limit: Option<u64>,
+    ) -> Page<JsonToken, FeedCursor> {
+        assert!(from_ms < to_ms, "from_ms must be before to_ms");
+        let category_feed;
+        let feed = if let Some(category_id) = category_id {
+            if let Some(feed) = self.certs_by_issued_at_per_category.get(&category_id) {
+                category_feed = feed;
+                &category_feed
+            } else {
+                return Page::empty();
+            }
+        } else {
+            &self.certs_by_issued_at
+        };
+        let lower = if let Some(after) = after {
+            Bound::Excluded((after.issued_at, after.token_id))
+        } else {
+            Bound::Included((from_ms, String::new()))
+        };
+        let upper: IssuanceKey = (to_ms, String::new());
+        if let Bound::Excluded(after) = &lower {
+            if *after >= upper {
+                return Page::empty();
+            }
+        }
+        let keys: Vec<IssuanceKey> = feed
+            .range((lower, Bound::Excluded(upper)))
+            .take(limit.unwrap_or(50) as usize + 1)
+            .map(|(key, _)| key)
+            .collect();
+        self.internal_feed_page(keys, limit.unwrap_or(50))
+    }
+}
+
+impl Contract {
+    //turn up to `limit + 1` feed keys into a page of `limit` certificates
+    fn internal_feed_page(
+        &self,
+        mut keys: Vec<IssuanceKey>,
+        limit: u64,
+    ) -> Page<JsonToken, FeedCursor> {
+        let has_more = keys.len() as u64 > limit;
+        keys.truncate(limit as usize);
+        let next_cursor = if has_more {
+            keys.last().map(|(issued_at, token_id)| FeedCursor {
+                issued_at: *issued_at,
+                token_id: token_id.clone(),
+            })
+        } else {
+            None
+        };
+        Page {
+            items: keys
+                .into_iter()
+                .map(|(_, token_id)| self.nft_token(token_id).unwrap())
+                .collect(),
+            next_cursor,
+        }
+    }
+}
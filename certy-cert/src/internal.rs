@@ -19,6 +19,50 @@ pub(crate) fn hash_category_id(category_id: &CategoryId) -> CryptoHash {
     hash
 }
 
+//used to generate a unique prefix in our storage collections (this is to avoid data collisions)
+pub(crate) fn hash_token_id(token_id: &TokenId) -> CryptoHash {
+    //get the default hash
+    let mut hash = CryptoHash::default();
+    //we hash the token ID and return it
+    hash.copy_from_slice(&env::sha256(token_id.as_bytes()));
+    hash
+}
+
+//used to generate a unique prefix in our storage collections (this is to avoid data collisions)
+pub(crate) fn hash_document_hash(document_hash: &[u8]) -> CryptoHash {
+    //get the default hash
+    let mut hash = CryptoHash::default();
+    //we hash the document hash and return it
+    hash.copy_from_slice(&env::sha256(document_hash));
+    hash
+}
+
+//the distinct document hashes a certificate can be looked up by
+pub(crate) fn document_hashes(metadata: &TokenMetadata) -> Vec<Vec<u8>> {
+    let mut hashes: Vec<Vec<u8>> = vec![];
+    for hash in [&metadata.media_hash, &metadata.reference_hash]
+        .iter()
+        .copied()
+        .flatten()
+    {
+        if !hashes.contains(&hash.0) {
+            hashes.push(hash.0.clone());
+        }
+    }
+    hashes
+}
+
+//used to generate a unique prefix in our storage collections (this is to avoid data collisions)
+pub(crate) fn hash_owner_category(account_id: &AccountId, category_id: &CategoryId) -> CryptoHash {
+    //get the default hash
+    let mut hash = CryptoHash::default();
+    //we hash the serialized pair and return it
+    hash.copy_from_slice(&env::sha256(
+        &(account_id, category_id).try_to_vec().unwrap(),
+    ));
+    hash
+}
+
 //used to make sure the user attached exactly 1 yoctoNEAR
 pub(crate) fn assert_one_yocto() {
     assert_eq!(
@@ -60,6 +104,82 @@ pub(crate) fn refund_deposit(storage_used: u64) {
 }
 
 impl Contract {
+    //take the protocol fee for `count` items out of the attached deposit, returns the deposit left for storage
+    pub(crate) fn internal_fee_collect(
+        &mut self,
+        account_id: &AccountId,
+        fee: Balance,
+        token_ids: Option<Vec<TokenId>>,
+        category_ids: Option<Vec<CategoryId>>,
+    ) -> Balance {
+        let attached_deposit = env::attached_deposit();
+        let count = token_ids.as_ref().map(|ids| ids.len()).unwrap_or(0)
+            + category_ids.as_ref().map(|ids| ids.len()).unwrap_or(0);
+        let amount = fee * count as u128;
+        //verified issuers don't pay the protocol fee
+        if amount == 0 || self.verified_issuers.get(account_id).is_some() {
+            return attached_deposit;
+        }
+        assert!(
+            amount <= attached_deposit,
+            "Must attach {} yoctoNEAR of fee on top of storage",
+            amount,
+        );
+        self.fees_collected += amount;
+        let fee_collect_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::FeeCollect(vec![FeeCollectLog {
+                account_id: account_id.to_string(),
+                amount: U128(amount),
+                token_ids,
+                category_ids,
+            }]),
+        };
+        fee_collect_log.emit();
+        attached_deposit - amount
+    }
+    //used to make sure the user is the contract owner
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Not contract owner"
+        );
+    }
+    //used to make sure the user is the contract owner or an appointed admin
+    pub(crate) fn assert_admin(&self) {
+        let account_id = env::predecessor_account_id();
+        assert!(
+            account_id == self.owner_id || self.admins.contains(&account_id),
+            "Not admin"
+        );
+    }
+    //used to make sure the user is the contract owner, an admin or an appointed verifier
+    pub(crate) fn assert_verifier(&self, account_id: &AccountId) {
+        assert!(
+            *account_id == self.owner_id
+                || self.admins.contains(account_id)
+                || self.verifiers.contains(account_id),
+            "Not verifier"
+        );
+    }
+    //used to make sure issuer operations are not stopped contract-wide
+    pub(crate) fn assert_not_paused(&self) {
+        assert!(!self.paused, "Contract paused");
+    }
+    //used to make sure issuer operations are not stopped for the category
+    pub(crate) fn assert_category_not_frozen(&self, category_id: &CategoryId) {
+        assert!(
+            !self.frozen_categories.contains(category_id),
+            "Category frozen"
+        );
+    }
+    //used to make sure issuer operations are not stopped for the category of the token
+    pub(crate) fn assert_token_not_frozen(&self, token_id: &TokenId) {
+        let token = self.tokens_by_id.get(token_id).expect("No token");
+        self.assert_category_not_frozen(&token.category_id);
+    }
     //used to make sure the user is the owner of the category
     pub(crate) fn assert_cert_provider(&self, account_id: AccountId, token_id: &TokenId) {
         let token = self.tokens_by_id.get(token_id).expect("No token");
@@ -125,7 +245,7 @@ impl Contract {
         let old_category_metadata = self.category_metadata_by_id.get(category_id).unwrap();
         category_metadata.updated_at = Some(env::block_timestamp_ms());
         self.category_metadata_by_id
-            .insert(&category_id, &category_metadata);
+            .insert(category_id, &category_metadata);
         let category_update_log: EventLog = EventLog {
             standard: CERTY_CERT_STANDARD_NAME.to_string(),
             version: CERTY_CERT_VERSION.to_string(),
@@ -151,6 +271,9 @@ impl Contract {
         );
         self.categories_by_id.remove(&category_id);
         self.category_metadata_by_id.remove(&category_id);
+        self.storage_payer_by_category.remove(&category_id);
+        self.category_prices.remove(&category_id);
+        self.arbiters_per_category.remove(&category_id);
         let category_delete_log: EventLog = EventLog {
             standard: CERTY_CERT_STANDARD_NAME.to_string(),
             version: CERTY_CERT_VERSION.to_string(),
@@ -168,8 +291,7 @@ impl Contract {
         receiver_id: AccountId,
         metadata: TokenMetadata,
         category_id: CategoryId,
-    ) {
-        //measure the initial storage being used on the contract
+    ) -> TokenId {
         let token_id: TokenId = self.count_token_id.to_string();
 
         //specify the token struct that contains the owner ID
@@ -197,6 +319,16 @@ impl Contract {
         //call the internal method for adding the token to the category
         self.internal_token_add_to_category(&token.category_id, &token_id);
 
+        self.internal_token_add_to_owner_category(&token.owner_id, &token.category_id, &token_id);
+
+        self.internal_token_add_to_feed(
+            &token.category_id,
+            &token_id,
+            cert_metadata.issued_at.unwrap(),
+        );
+        self.internal_token_add_to_document_hashes(&token_id, &cert_metadata);
+        self.internal_status_index_assign(&token.category_id, &token_id);
+
         // Construct the mint log as per the events standard.
         let nft_mint_log: EventLog = EventLog {
             // Standard name ("nep171").
@@ -218,14 +350,48 @@ impl Contract {
 
         // Log the serialized json.
         nft_mint_log.emit();
+
+        token_id
     }
     //update token
-    pub(crate) fn internal_token_update(&mut self, token_id: &TokenId, metadata: &TokenMetadata) {
+    pub(crate) fn internal_token_update(
+        &mut self,
+        token_id: &TokenId,
+        metadata: &TokenMetadata,
+        reason: Option<String>,
+    ) {
         let old_metadata = self.token_metadata_by_id.get(token_id).unwrap();
         let mut cert_metadata = metadata.clone();
         cert_metadata.updated_at = Some(env::block_timestamp_ms());
-        self.token_metadata_by_id.insert(&token_id, &cert_metadata);
-        
+        self.token_metadata_by_id.insert(token_id, &cert_metadata);
+
+        //let the holder know that the certificate was altered
+        let token = self.tokens_by_id.get(token_id).expect("No Token");
+        self.internal_token_remove_from_document_hashes(token_id, &old_metadata);
+        self.internal_token_add_to_document_hashes(token_id, &cert_metadata);
+        //keep the issuance feeds ordered by the new `issued_at`
+        if old_metadata.issued_at != cert_metadata.issued_at {
+            self.internal_token_remove_from_feed(
+                &token.category_id,
+                token_id,
+                old_metadata.issued_at.unwrap_or(0),
+            );
+            self.internal_token_add_to_feed(
+                &token.category_id,
+                token_id,
+                cert_metadata.issued_at.unwrap_or(0),
+            );
+        }
+        self.internal_cert_change_add(
+            &token.owner_id,
+            token_id,
+            &CertChange {
+                authorized_id: env::predecessor_account_id(),
+                reason: reason.clone(),
+                updated_at: env::block_timestamp_ms(),
+            },
+        );
+
          // Construct the update log as per the events standard.
          let nft_update_log: EventLog = EventLog {
             // Standard name ("nep171").
@@ -238,6 +404,7 @@ impl Contract {
                 token_ids: vec![token_id.to_string()],
                 old_token_metadatas: vec![old_metadata],
                 new_token_metadatas: vec![cert_metadata],
+                reason,
             }]),
         };
 
@@ -259,7 +426,7 @@ impl Contract {
                 UnorderedSet::new(
                     StorageKey::CategoryPerOwnerInner {
                         //we get a new unique prefix for the collection
-                        account_id_hash: hash_account_id(&account_id),
+                        account_id_hash: hash_account_id(account_id),
                     }
                     .try_to_vec()
                     .unwrap(),
@@ -278,8 +445,28 @@ impl Contract {
         let token = self.tokens_by_id.get(&token_id).expect("No Token");
         self.internal_token_remove_from_owner(&token.owner_id, &token_id);
         self.internal_token_remove_from_category(&token.category_id, &token_id);
+        self.internal_token_remove_from_owner_category(
+            &token.owner_id,
+            &token.category_id,
+            &token_id,
+        );
+        let metadata = self.token_metadata_by_id.get(&token_id).unwrap();
+        self.internal_token_remove_from_feed(
+            &token.category_id,
+            &token_id,
+            metadata.issued_at.unwrap_or(0),
+        );
+        self.internal_token_remove_from_document_hashes(&token_id, &metadata);
+        //a burned certificate reads as revoked to anyone holding its credential
+        if self.status_index_by_token.contains_key(&token_id) {
+            self.internal_status_set(&token_id, StatusPurpose::Revocation, true);
+            self.status_index_by_token.remove(&token_id);
+        }
+        self.internal_suspension_remove(&token.category_id, &token_id);
+        self.internal_cert_change_remove(&token.owner_id, &token_id);
         self.tokens_by_id.remove(&token_id);
         self.token_metadata_by_id.remove(&token_id);
+        self.storage_payer_by_token.remove(&token_id);
         //TO-DO: add the event log.
     }
     //remove a category from an owner (internal method and can't be called directly via CLI).
@@ -322,7 +509,7 @@ impl Contract {
                 UnorderedSet::new(
                     StorageKey::TokenPerCategoryInner {
                         //we get a new unique prefix for the collection
-                        category_id_hash: hash_category_id(&category_id),
+                        category_id_hash: hash_category_id(category_id),
                     }
                     .try_to_vec()
                     .unwrap(),
@@ -371,7 +558,7 @@ impl Contract {
             UnorderedSet::new(
                 StorageKey::TokenPerOwnerInner {
                     //we get a new unique prefix for the collection
-                    account_id_hash: hash_account_id(&account_id),
+                    account_id_hash: hash_account_id(account_id),
                 }
                 .try_to_vec()
                 .unwrap(),
@@ -409,4 +596,314 @@ impl Contract {
             self.tokens_per_owner.insert(account_id, &tokens_set);
         }
     }
+
+    //add an existing token to the indexes derived from it, adding it again changes nothing
+    pub(crate) fn internal_token_index(&mut self, token_id: &TokenId) {
+        let token = self.tokens_by_id.get(token_id).expect("No Token");
+        self.internal_token_add_to_owner_category(&token.owner_id, &token.category_id, token_id);
+        //certificates minted before `issued_at` was set are listed first in the feeds
+        let metadata = self.token_metadata_by_id.get(token_id).unwrap();
+        self.internal_token_add_to_feed(
+            &token.category_id,
+            token_id,
+            metadata.issued_at.unwrap_or(0),
+        );
+        self.internal_token_add_to_document_hashes(token_id, &metadata);
+        self.internal_status_index_assign(&token.category_id, token_id);
+    }
+    //add a token to the sets of tokens of its document hashes
+    pub(crate) fn internal_token_add_to_document_hashes(
+        &mut self,
+        token_id: &TokenId,
+        metadata: &TokenMetadata,
+    ) {
+        for document_hash in document_hashes(metadata) {
+            let mut tokens_set = self
+                .tokens_per_document_hash
+                .get(&document_hash)
+                .unwrap_or_else(|| {
+                    UnorderedSet::new(
+                        StorageKey::TokensPerDocumentHashInner {
+                            document_hash_hash: hash_document_hash(&document_hash),
+                        }
+                        .try_to_vec()
+                        .unwrap(),
+                    )
+                });
+            tokens_set.insert(token_id);
+            self.tokens_per_document_hash
+                .insert(&document_hash, &tokens_set);
+        }
+    }
+    //remove a token from the sets of tokens of its document hashes
+    pub(crate) fn internal_token_remove_from_document_hashes(
+        &mut self,
+        token_id: &TokenId,
+        metadata: &TokenMetadata,
+    ) {
+        for document_hash in document_hashes(metadata) {
+            if let Some(mut tokens_set) = self.tokens_per_document_hash.get(&document_hash) {
+                tokens_set.remove(token_id);
+                if tokens_set.is_empty() {
+                    self.tokens_per_document_hash.remove(&document_hash);
+                } else {
+                    self.tokens_per_document_hash
+                        .insert(&document_hash, &tokens_set);
+                }
+            }
+        }
+    }
+    //add a token to the global issuance feed and to the feed of its category
+    pub(crate) fn internal_token_add_to_feed(
+        &mut self,
+        category_id: &CategoryId,
+        token_id: &TokenId,
+        issued_at: u64,
+    ) {
+        let key: IssuanceKey = (issued_at, token_id.clone());
+        self.certs_by_issued_at.insert(&key, &());
+        let mut category_feed = self
+            .certs_by_issued_at_per_category
+            .get(category_id)
+            .unwrap_or_else(|| {
+                TreeMap::new(
+                    StorageKey::CertsByIssuedAtPerCategoryInner {
+                        category_id_hash: hash_category_id(category_id),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+        category_feed.insert(&key, &());
+        self.certs_by_issued_at_per_category
+            .insert(category_id, &category_feed);
+    }
+    //remove a token from the global issuance feed and from the feed of its category
+    pub(crate) fn internal_token_remove_from_feed(
+        &mut self,
+        category_id: &CategoryId,
+        token_id: &TokenId,
+        issued_at: u64,
+    ) {
+        let key: IssuanceKey = (issued_at, token_id.clone());
+        self.certs_by_issued_at.remove(&key);
+        if let Some(mut category_feed) = self.certs_by_issued_at_per_category.get(category_id) {
+            category_feed.remove(&key);
+            if category_feed.is_empty() {
+                self.certs_by_issued_at_per_category.remove(category_id);
+            } else {
+                self.certs_by_issued_at_per_category
+                    .insert(category_id, &category_feed);
+            }
+        }
+    }
+    //add a token to the set of tokens an owner holds from a category
+    pub(crate) fn internal_token_add_to_owner_category(
+        &mut self,
+        account_id: &AccountId,
+        category_id: &CategoryId,
+        token_id: &TokenId,
+    ) {
+        let key = (account_id.clone(), category_id.clone());
+        let mut tokens_set = self.tokens_per_owner_category.get(&key).unwrap_or_else(|| {
+            UnorderedSet::new(
+                StorageKey::TokensPerOwnerCategoryInner {
+                    owner_category_hash: hash_owner_category(account_id, category_id),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        tokens_set.insert(token_id);
+        self.tokens_per_owner_category.insert(&key, &tokens_set);
+    }
+    //remove a token from the set of tokens an owner holds from a category
+    pub(crate) fn internal_token_remove_from_owner_category(
+        &mut self,
+        account_id: &AccountId,
+        category_id: &CategoryId,
+        token_id: &TokenId,
+    ) {
+        let key = (account_id.clone(), category_id.clone());
+        if let Some(mut tokens_set) = self.tokens_per_owner_category.get(&key) {
+            tokens_set.remove(token_id);
+            if tokens_set.is_empty() {
+                self.tokens_per_owner_category.remove(&key);
+            } else {
+                self.tokens_per_owner_category.insert(&key, &tokens_set);
+            }
+        }
+    }
+
+    //record a change on a certificate for its holder, after the ones not acknowledged yet
+    pub(crate) fn internal_cert_change_add(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TokenId,
+        change: &CertChange,
+    ) {
+        //get the changes for the given holder
+        let mut changes = self
+            .cert_changes_per_holder
+            .get(account_id)
+            .unwrap_or_else(|| {
+                //if the holder doesn't have any changes, we create a new unordered map
+                UnorderedMap::new(
+                    StorageKey::CertChangesPerHolderInner {
+                        //we get a new unique prefix for the collection
+                        account_id_hash: hash_account_id(account_id),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+
+        let mut token_changes = changes.get(token_id).unwrap_or_default();
+        token_changes.push(change.clone());
+        changes.insert(token_id, &token_changes);
+
+        self.cert_changes_per_holder.insert(account_id, &changes);
+    }
+    //remove the oldest unacknowledged change on a certificate from its holder, returns the removed change
+    pub(crate) fn internal_cert_change_pop(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TokenId,
+    ) -> Option<CertChange> {
+        let mut changes = self.cert_changes_per_holder.get(account_id)?;
+
+        let mut token_changes = changes.get(token_id)?;
+        let change = token_changes.remove(0);
+        if token_changes.is_empty() {
+            changes.remove(token_id);
+        } else {
+            changes.insert(token_id, &token_changes);
+        }
+
+        //if there are no changes left, we remove the holder from the cert_changes_per_holder collection
+        if changes.is_empty() {
+            self.cert_changes_per_holder.remove(account_id);
+        } else {
+            self.cert_changes_per_holder.insert(account_id, &changes);
+        }
+        Some(change)
+    }
+    //remove all the unacknowledged changes on a certificate from its holder
+    pub(crate) fn internal_cert_change_remove(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TokenId,
+    ) {
+        if let Some(mut changes) = self.cert_changes_per_holder.get(account_id) {
+            changes.remove(token_id);
+            if changes.is_empty() {
+                self.cert_changes_per_holder.remove(account_id);
+            } else {
+                self.cert_changes_per_holder.insert(account_id, &changes);
+            }
+        }
+    }
+    //add or replace the endorsement of an endorser on a token
+    pub(crate) fn internal_endorsement_add(
+        &mut self,
+        token_id: &TokenId,
+        endorser_id: &AccountId,
+        endorsement: &Endorsement,
+    ) {
+        //get the endorsements for the given token
+        let mut endorsements = self
+            .endorsements_per_token
+            .get(token_id)
+            .unwrap_or_else(|| {
+                //if the token doesn't have any endorsements, we create a new unordered map
+                UnorderedMap::new(
+                    StorageKey::EndorsementsPerTokenInner {
+                        //we get a new unique prefix for the collection
+                        token_id_hash: hash_token_id(token_id),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+
+        endorsements.insert(endorser_id, endorsement);
+
+        self.endorsements_per_token.insert(token_id, &endorsements);
+    }
+    //remove the endorsement of an endorser from a token, returns whether there was one
+    pub(crate) fn internal_endorsement_remove(
+        &mut self,
+        token_id: &TokenId,
+        endorser_id: &AccountId,
+    ) -> bool {
+        let mut endorsements = match self.endorsements_per_token.get(token_id) {
+            Some(endorsements) => endorsements,
+            None => return false,
+        };
+
+        if endorsements.remove(endorser_id).is_none() {
+            return false;
+        }
+
+        //if there are no endorsements left, we remove the token from the endorsements_per_token collection
+        if endorsements.is_empty() {
+            self.endorsements_per_token.remove(token_id);
+        } else {
+            self.endorsements_per_token.insert(token_id, &endorsements);
+        }
+        true
+    }
+    //remove all the endorsements of a token, each endorser gets back the storage it paid for
+    pub(crate) fn internal_endorsements_release(&mut self, token_id: &TokenId) {
+        let endorser_ids: Vec<AccountId> = match self.endorsements_per_token.get(token_id) {
+            Some(endorsements) => endorsements.keys().collect(),
+            None => return,
+        };
+        for endorser_id in endorser_ids.iter() {
+            let initial_storage_usage = env::storage_usage();
+            self.internal_endorsement_remove(token_id, endorser_id);
+            self.internal_storage_settle_from(endorser_id, initial_storage_usage, 0);
+        }
+    }
+    //add an account to the set of accounts an accreditor has accredited
+    pub(crate) fn internal_accredited_add_to_accreditor(
+        &mut self,
+        accreditor_id: &AccountId,
+        account_id: &AccountId,
+    ) {
+        let mut accredited_set = self
+            .accredited_per_accreditor
+            .get(accreditor_id)
+            .unwrap_or_else(|| {
+                UnorderedSet::new(
+                    StorageKey::AccreditedPerAccreditorInner {
+                        account_id_hash: hash_account_id(accreditor_id),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+        accredited_set.insert(account_id);
+        self.accredited_per_accreditor
+            .insert(accreditor_id, &accredited_set);
+    }
+    //remove an account from the set of accounts an accreditor has accredited
+    pub(crate) fn internal_accredited_remove_from_accreditor(
+        &mut self,
+        accreditor_id: &AccountId,
+        account_id: &AccountId,
+    ) {
+        let mut accredited_set = self
+            .accredited_per_accreditor
+            .get(accreditor_id)
+            .expect("Account should be accredited by the accreditor");
+        accredited_set.remove(account_id);
+
+        if accredited_set.is_empty() {
+            self.accredited_per_accreditor.remove(accreditor_id);
+        } else {
+            self.accredited_per_accreditor
+                .insert(accreditor_id, &accredited_set);
+        }
+    }
 }
@@ -0,0 +1,397 @@
+use crate::*;
+use ed25519_dalek::Verifier;
+use near_sdk::{bs58, CurveType, PublicKey};
+use std::convert::TryFrom;
+
+pub const ED25519_2020_KEY_TYPE: &str = "Ed25519VerificationKey2020";
+//multicodec prefix of an ed25519 public key in `publicKeyMultibase`
+pub(crate) const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+//ed25519 key an issuer signs certificates with off-chain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IssuerKey {
+    pub key_id: String,        // ex. "pki-2023"
+    pub public_key: PublicKey, // ex. "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+    pub valid_from: u64,       // When the key starts signing, Unix epoch in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<u64>, // When the key stops signing, Unix epoch in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<u64>, // When the key was revoked, Unix epoch in milliseconds
+}
+
+impl IssuerKey {
+    //whether signatures made at `at_ms` are covered by the key
+    pub(crate) fn is_active_at(&self, at_ms: u64) -> bool {
+        self.valid_from <= at_ms
+            && self
+                .valid_until
+                .map(|valid_until| at_ms < valid_until)
+                .unwrap_or(true)
+            && self
+                .revoked_at
+                .map(|revoked_at| at_ms < revoked_at)
+                .unwrap_or(true)
+    }
+    //`publicKeyMultibase` of the key: base58btc of the multicodec prefixed key
+    pub(crate) fn public_key_multibase(&self) -> String {
+        let mut bytes = ED25519_MULTICODEC.to_vec();
+        bytes.extend_from_slice(&self.public_key.as_bytes()[1..]);
+        format!("z{}", bs58::encode(bytes).into_string())
+    }
+}
+
+//Result of checking a signature against the keys of an issuer
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SignatureCheck {
+    //whether one of the keys of the issuer made the signature
+    pub valid: bool,
+    //the key that made the signature
+    pub key_id: Option<String>,
+    //whether that key was active at `signed_at`
+    pub active_at_signing: bool,
+    //signing time taken from the signed bytes
+    pub signed_at: u64,
+}
+
+//What an issuer signs to vouch for a certificate
+#[derive(BorshSerialize)]
+pub struct CertSigningPayload {
+    pub contract_id: AccountId,
+    pub token_id: TokenId,
+    pub owner_id: AccountId,
+    pub category_id: CategoryId,
+    pub metadata: TokenMetadata,
+    pub signed_at: u64, // When the issuer signed, Unix epoch in milliseconds
+}
+
+//What an issuer signs to vouch for an off-chain message, ex. a Verifiable Credential
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct IssuerSigningPayload {
+    pub signed_at: u64, // When the issuer signed, Unix epoch in milliseconds
+    pub message: Vec<u8>,
+}
+
+#[near_bindgen]
+impl Contract {
+    //Category owner registers an ed25519 key, active from `valid_from` (now if not given)
+    //until `valid_until` if given
+    #[payable]
+    pub fn issuer_key_add(
+        &mut self,
+        key_id: String,
+        public_key: PublicKey,
+        valid_from: Option<u64>,
+        valid_until: Option<u64>,
+    ) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        assert_valid_fragment(&key_id);
+        let account_id = env::predecessor_account_id();
+        assert!(
+            self.categories_per_owner.contains_key(&account_id),
+            "Not category owner"
+        );
+        assert!(
+            public_key.curve_type() == CurveType::ED25519,
+            "Only ed25519 keys are supported"
+        );
+        let valid_from = valid_from.unwrap_or_else(env::block_timestamp_ms);
+        if let Some(valid_until) = valid_until {
+            assert!(
+                valid_from < valid_until,
+                "valid_from must be before valid_until"
+            );
+        }
+        let initial_storage_usage = env::storage_usage();
+        let mut keys = self.issuer_keys.get(&account_id).unwrap_or_default();
+        assert!(
+            !keys.iter().any(|key| key.key_id == key_id),
+            "Key already exists"
+        );
+        //key ids are fragments of the DID document of the issuer
+        assert!(
+            !self
+                .did_records
+                .get(&account_id)
+                .map(|record| record
+                    .verification_methods
+                    .iter()
+                    .any(|method| method.id == key_id))
+                .unwrap_or(false),
+            "Key already exists"
+        );
+        keys.push(IssuerKey {
+            key_id: key_id.clone(),
+            public_key,
+            valid_from,
+            valid_until,
+            revoked_at: None,
+        });
+        self.issuer_keys.insert(&account_id, &keys);
+        let issuer_key_add_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::IssuerKeyAdd(vec![IssuerKeyLog {
+                account_id: account_id.to_string(),
+                key_ids: vec![key_id],
+            }]),
+        };
+        issuer_key_add_log.emit();
+        self.internal_storage_settle(&account_id, initial_storage_usage);
+    }
+    //Issuer revokes a key, signatures made before the revocation stay valid
+    #[payable]
+    pub fn issuer_key_revoke(&mut self, key_id: String) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        let account_id = env::predecessor_account_id();
+        let mut keys = self.issuer_keys.get(&account_id).unwrap_or_default();
+        let key = keys
+            .iter_mut()
+            .find(|key| key.key_id == key_id)
+            .expect("No key");
+        assert!(key.revoked_at.is_none(), "Key already revoked");
+        let initial_storage_usage = env::storage_usage();
+        key.revoked_at = Some(env::block_timestamp_ms());
+        self.issuer_keys.insert(&account_id, &keys);
+        let issuer_key_revoke_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::IssuerKeyRevoke(vec![IssuerKeyLog {
+                account_id: account_id.to_string(),
+                key_ids: vec![key_id],
+            }]),
+        };
+        issuer_key_revoke_log.emit();
+        self.internal_storage_settle(&account_id, initial_storage_usage);
+    }
+    pub fn issuer_keys(&self, account_id: AccountId) -> Vec<IssuerKey> {
+        self.issuer_keys.get(&account_id).unwrap_or_default()
+    }
+    //Bytes the category owner signs to vouch for a certificate: the borsh serialization of
+    //the contract, token, owner, category and metadata of the certificate and `signed_at`
+    pub fn cert_signing_payload(&self, token_id: TokenId, signed_at: u64) -> Option<Base64VecU8> {
+        self.internal_cert_signing_payload(&token_id, signed_at)
+            .map(|payload| Base64VecU8(payload.try_to_vec().unwrap()))
+    }
+    //Check a signature of the category owner over the signing payload of a certificate made at `signed_at`
+    pub fn cert_verify_signature(
+        &self,
+        token_id: TokenId,
+        signature: Base64VecU8,
+        signed_at: u64,
+    ) -> SignatureCheck {
+        let payload = self
+            .internal_cert_signing_payload(&token_id, signed_at)
+            .expect("No token");
+        let category = self.categories_by_id.get(&payload.category_id).unwrap();
+        self.internal_verify_signature(
+            &category.owner_id,
+            &payload.try_to_vec().unwrap(),
+            &signature.0,
+            signed_at,
+        )
+    }
+    //Bytes an issuer signs to vouch for an off-chain message: the borsh serialization of
+    //`signed_at` and the message
+    pub fn issuer_signing_payload(&self, message: Base64VecU8, signed_at: u64) -> Base64VecU8 {
+        Base64VecU8(
+            IssuerSigningPayload {
+                signed_at,
+                message: message.0,
+            }
+            .try_to_vec()
+            .unwrap(),
+        )
+    }
+    //Check a signature of an issuer over an `issuer_signing_payload`, the signing time is read
+    //from the signed bytes
+    pub fn issuer_verify_signature(
+        &self,
+        account_id: AccountId,
+        payload: Base64VecU8,
+        signature: Base64VecU8,
+    ) -> SignatureCheck {
+        let signed_at = IssuerSigningPayload::try_from_slice(&payload.0)
+            .expect("Invalid signing payload")
+            .signed_at;
+        self.internal_verify_signature(&account_id, &payload.0, &signature.0, signed_at)
+    }
+}
+
+impl Contract {
+    fn internal_cert_signing_payload(
+        &self,
+        token_id: &TokenId,
+        signed_at: u64,
+    ) -> Option<CertSigningPayload> {
+        let token = self.tokens_by_id.get(token_id)?;
+        Some(CertSigningPayload {
+            contract_id: env::current_account_id(),
+            token_id: token_id.clone(),
+            owner_id: token.owner_id,
+            category_id: token.category_id,
+            metadata: self.token_metadata_by_id.get(token_id).unwrap(),
+            signed_at,
+        })
+    }
+    //the key of the issuer that made the signature, preferring one active at `signed_at`
+    fn internal_verify_signature(
+        &self,
+        account_id: &AccountId,
+        message: &[u8],
+        signature: &[u8],
+        signed_at: u64,
+    ) -> SignatureCheck {
+        let signature = ed25519_dalek::Signature::try_from(signature).ok();
+        let signer_keys: Vec<IssuerKey> = self
+            .issuer_keys
+            .get(account_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|key| {
+                let public_key =
+                    ed25519_dalek::PublicKey::from_bytes(&key.public_key.as_bytes()[1..]);
+                match (public_key, signature.as_ref()) {
+                    (Ok(public_key), Some(signature)) => {
+                        public_key.verify(message, signature).is_ok()
+                    }
+                    _ => false,
+                }
+            })
+            .collect();
+        let signer_key = signer_keys
+            .iter()
+            .find(|key| key.is_active_at(signed_at))
+            .or_else(|| signer_keys.first());
+        SignatureCheck {
+            valid: signer_key.is_some(),
+            key_id: signer_key.map(|key| key.key_id.clone()),
+            active_at_signing: signer_key
+                .map(|key| key.is_active_at(signed_at))
+                .unwrap_or(false),
+            signed_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::context_at;
+    use ed25519_dalek::{Keypair, SecretKey, Signer};
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    //call at `now_ms` with 1 NEAR attached
+    fn context(predecessor: usize, now_ms: u64) -> VMContextBuilder {
+        context_at(predecessor, 10u128.pow(24), now_ms)
+    }
+
+    //fixed ed25519 keypair from a given seed
+    fn keypair(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = (&secret).into();
+        Keypair { secret, public }
+    }
+
+    fn near_public_key(keypair: &Keypair) -> PublicKey {
+        let mut bytes = vec![CurveType::ED25519 as u8];
+        bytes.extend_from_slice(keypair.public.as_bytes());
+        PublicKey::try_from(bytes).unwrap()
+    }
+
+    //category "1" of accounts(1) with key "pki" valid from 1_000 and certificate "0" minted to accounts(2)
+    fn setup() -> Contract {
+        let mut contract = crate::test_utils::setup();
+        testing_env!(context(1, 500).build());
+        contract.issuer_key_add(
+            "pki".to_string(),
+            near_public_key(&keypair(1)),
+            Some(1_000),
+            None,
+        );
+        contract
+    }
+
+    fn sign_cert(contract: &Contract, keypair: &Keypair, signed_at: u64) -> Base64VecU8 {
+        let payload = contract
+            .cert_signing_payload("0".to_string(), signed_at)
+            .unwrap();
+        Base64VecU8(keypair.sign(&payload.0).to_bytes().to_vec())
+    }
+
+    #[test]
+    fn cert_signature_of_active_key_is_valid() {
+        let contract = setup();
+        let signature = sign_cert(&contract, &keypair(1), 2_000);
+        let check = contract.cert_verify_signature("0".to_string(), signature, 2_000);
+        assert!(check.valid);
+        assert!(check.active_at_signing);
+        assert_eq!(check.key_id, Some("pki".to_string()));
+        assert_eq!(check.signed_at, 2_000);
+    }
+
+    #[test]
+    fn cert_signature_with_other_signed_at_is_not_valid() {
+        let contract = setup();
+        //signed before `valid_from`, passing a later time doesn't match the signed bytes
+        let signature = sign_cert(&contract, &keypair(1), 500);
+        let check = contract.cert_verify_signature("0".to_string(), signature, 2_000);
+        assert!(!check.valid);
+        assert!(check.key_id.is_none());
+    }
+
+    #[test]
+    fn cert_signature_of_wrong_key_is_not_valid() {
+        let contract = setup();
+        let signature = sign_cert(&contract, &keypair(2), 2_000);
+        let check = contract.cert_verify_signature("0".to_string(), signature, 2_000);
+        assert!(!check.valid);
+        assert!(!check.active_at_signing);
+        assert!(check.key_id.is_none());
+    }
+
+    #[test]
+    fn cert_signature_before_valid_from_is_not_active() {
+        let contract = setup();
+        let signature = sign_cert(&contract, &keypair(1), 500);
+        let check = contract.cert_verify_signature("0".to_string(), signature, 500);
+        assert!(check.valid);
+        assert!(!check.active_at_signing);
+    }
+
+    #[test]
+    fn signature_of_revoked_key_is_active_only_before_revocation() {
+        let mut contract = setup();
+        testing_env!(context(1, 3_000).build());
+        contract.issuer_key_revoke("pki".to_string());
+
+        let signature = sign_cert(&contract, &keypair(1), 2_000);
+        let check = contract.cert_verify_signature("0".to_string(), signature, 2_000);
+        assert!(check.valid);
+        assert!(check.active_at_signing);
+
+        let signature = sign_cert(&contract, &keypair(1), 4_000);
+        let check = contract.cert_verify_signature("0".to_string(), signature, 4_000);
+        assert!(check.valid);
+        assert!(!check.active_at_signing);
+    }
+
+    #[test]
+    fn issuer_signature_reads_signed_at_from_the_payload() {
+        let contract = setup();
+        let payload = contract.issuer_signing_payload(Base64VecU8(b"credential".to_vec()), 2_000);
+        let signature = Base64VecU8(keypair(1).sign(&payload.0).to_bytes().to_vec());
+        let check = contract.issuer_verify_signature(accounts(1), payload.clone(), signature);
+        assert!(check.valid);
+        assert!(check.active_at_signing);
+        assert_eq!(check.signed_at, 2_000);
+
+        let signature = Base64VecU8(keypair(2).sign(&payload.0).to_bytes().to_vec());
+        let check = contract.issuer_verify_signature(accounts(1), payload, signature);
+        assert!(!check.valid);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::*;
+
+//Verification of a category owner as a known institution
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IssuerVerification {
+    pub display_name: String,       // ex. "Amazon Web Services"
+    pub domain: String,             // ex. "aws.amazon.com"
+    pub evidence_reference: String, // URL to an off-chain document proving the identity of the institution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of the evidence document
+    pub verified_by: AccountId,     // owner or verifier who verified the institution
+    pub verified_at: u64,           // When the institution was verified, Unix epoch in milliseconds
+}
+
+//The Json issuer verification is what will be returned from view calls.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonIssuerVerification {
+    pub account_id: AccountId,
+    #[serde(flatten)]
+    pub verification: IssuerVerification,
+}
+
+#[near_bindgen]
+impl Contract {
+    //Contract owner or admins appoint an account allowed to verify institutions
+    #[payable]
+    pub fn verifier_add(&mut self, account_id: AccountId) {
+        assert_at_least_one_yocto();
+        self.assert_admin();
+        let initial_storage_usage = env::storage_usage();
+        assert!(self.verifiers.insert(&account_id), "Already a verifier");
+        let verifier_add_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::VerifierAdd(vec![VerifierLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_ids: vec![account_id.to_string()],
+            }]),
+        };
+        verifier_add_log.emit();
+        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
+        refund_deposit(required_storage_in_bytes);
+    }
+    #[payable]
+    pub fn verifier_remove(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        self.assert_admin();
+        assert!(self.verifiers.remove(&account_id), "Not a verifier");
+        let verifier_remove_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::VerifierRemove(vec![VerifierLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_ids: vec![account_id.to_string()],
+            }]),
+        };
+        verifier_remove_log.emit();
+    }
+    //Mark a category owner as a verified institution, replacing any previous verification
+    #[payable]
+    pub fn issuer_verify(
+        &mut self,
+        account_id: AccountId,
+        display_name: String,
+        domain: String,
+        evidence_reference: String,
+        evidence_hash: Option<Base64VecU8>,
+    ) {
+        assert_at_least_one_yocto();
+        self.assert_verifier(&env::predecessor_account_id());
+        //the verifier of the replaced verification gets back the storage it paid for
+        let initial_storage_usage = env::storage_usage();
+        if let Some(old_verification) = self.verified_issuers.remove(&account_id) {
+            self.internal_storage_settle_from(
+                &old_verification.verified_by,
+                initial_storage_usage,
+                0,
+            );
+        }
+        let initial_storage_usage = env::storage_usage();
+        let verification = IssuerVerification {
+            display_name,
+            domain,
+            evidence_reference,
+            evidence_hash,
+            verified_by: env::predecessor_account_id(),
+            verified_at: env::block_timestamp_ms(),
+        };
+        self.verified_issuers.insert(&account_id, &verification);
+        let issuer_verify_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::IssuerVerify(vec![IssuerVerifyLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_id: account_id.to_string(),
+                verification,
+            }]),
+        };
+        issuer_verify_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    #[payable]
+    pub fn issuer_revoke_verification(&mut self, account_id: AccountId, reason: Option<String>) {
+        assert_one_yocto();
+        self.assert_verifier(&env::predecessor_account_id());
+        let initial_storage_usage = env::storage_usage();
+        let verification = self
+            .verified_issuers
+            .remove(&account_id)
+            .expect("Issuer not verified");
+        let issuer_revoke_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::IssuerVerificationRevoke(vec![IssuerVerificationRevokeLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                account_ids: vec![account_id.to_string()],
+                reason,
+            }]),
+        };
+        issuer_revoke_log.emit();
+        //the freed storage goes back to the verifier who paid for it
+        self.internal_storage_settle_from(&verification.verified_by, initial_storage_usage, 0);
+    }
+    pub fn issuer_verification(&self, account_id: AccountId) -> Option<JsonIssuerVerification> {
+        self.verified_issuers
+            .get(&account_id)
+            .map(|verification| JsonIssuerVerification {
+                account_id,
+                verification,
+            })
+    }
+    pub fn is_verified_issuer(&self, account_id: AccountId) -> bool {
+        self.verified_issuers.get(&account_id).is_some()
+    }
+    //Query for all the verified institutions
+    pub fn verified_issuers(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonIssuerVerification> {
+        paginate_map(&self.verified_issuers, from_index, limit)
+            .items
+            .into_iter()
+            .map(|(account_id, verification)| JsonIssuerVerification {
+                account_id,
+                verification,
+            })
+            .collect()
+    }
+    pub fn verifiers(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+        paginate(self.verifiers.as_vector(), from_index, limit).items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{context, setup};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    //verifiers accounts(3) and accounts(4), both registered for storage, and accounts(1) verified by accounts(3)
+    fn verified_setup() -> Contract {
+        let mut contract = setup();
+        testing_env!(context(0, 10u128.pow(24)).build());
+        contract.verifier_add(accounts(3));
+        contract.verifier_add(accounts(4));
+        for verifier in 3..5 {
+            testing_env!(context(verifier, 10u128.pow(24)).build());
+            contract.storage_deposit(None, None);
+        }
+        verify(&mut contract, 3);
+        contract
+    }
+
+    fn verify(contract: &mut Contract, verifier: usize) {
+        testing_env!(context(verifier, 10u128.pow(24)).build());
+        contract.issuer_verify(
+            accounts(1),
+            "Certy University".to_string(),
+            "certy.example".to_string(),
+            "https://certy.example/accreditation.pdf".to_string(),
+            None,
+        );
+    }
+
+    fn available(contract: &Contract, account: usize) -> Balance {
+        contract
+            .storage_balance_of(accounts(account))
+            .unwrap()
+            .available
+            .0
+    }
+
+    #[test]
+    fn issuer_verify_again_refunds_the_previous_verifier() {
+        let mut contract = verified_setup();
+        let initial_available = available(&contract, 3);
+        verify(&mut contract, 4);
+        assert!(available(&contract, 3) > initial_available);
+        assert_eq!(
+            contract
+                .issuer_verification(accounts(1))
+                .unwrap()
+                .verification
+                .verified_by,
+            accounts(4)
+        );
+    }
+
+    #[test]
+    fn issuer_revoke_verification_refunds_the_verifier() {
+        let mut contract = verified_setup();
+        let (initial_available, other_available) =
+            (available(&contract, 3), available(&contract, 4));
+        testing_env!(context(4, 1).build());
+        contract.issuer_revoke_verification(accounts(1), None);
+        assert!(available(&contract, 3) > initial_available);
+        assert_eq!(available(&contract, 4), other_available);
+        assert!(!contract.is_verified_issuer(accounts(1)));
+    }
+}
@@ -1,25 +1,68 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
-use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::collections::{LazyOption, LookupMap, TreeMap, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{env, near_bindgen, AccountId, Balance, CryptoHash, PanicOnDefault, Promise};
 
+pub use certy_common::*;
+pub use crate::accreditation::*;
+pub use crate::appeal::*;
 pub use crate::category::*;
+pub use crate::cert_change::*;
+pub use crate::claim::*;
+pub use crate::credential::*;
+pub use crate::did::*;
+pub use crate::endorsement::*;
 pub use crate::events::*;
+pub use crate::fee::*;
+pub use crate::feed::*;
 use crate::internal::*;
+pub use crate::issuer_key::*;
+pub use crate::issuer_registry::*;
+#[allow(unused_imports)]
 pub use crate::manage_category::*;
+#[allow(unused_imports)]
 pub use crate::manage_cert::*;
 pub use crate::metadata::*;
 pub use crate::nft_core::*;
+pub use crate::open_badges::*;
+pub use crate::quote::*;
+pub use crate::status_list::*;
+pub use crate::suspension::*;
+pub use crate::upgrade::*;
+pub use crate::verification::*;
 
+mod accreditation;
+mod admin;
+mod appeal;
 mod category;
+mod cert_change;
+mod claim;
+mod credential;
+mod did;
+mod endorsement;
 mod enumeration;
 mod events;
+mod fee;
+mod feed;
 mod internal;
+mod issuer_key;
+mod issuer_registry;
 mod manage_category;
 mod manage_cert;
 mod metadata;
 mod nft_core;
+mod open_badges;
+mod pause;
+mod quote;
+mod sponsorship;
+mod status_list;
+mod storage;
+mod suspension;
+mod upgrade;
+mod verification;
+#[cfg(test)]
+pub(crate) mod test_utils;
 
 /// This spec can be treated like a version of the standard.
 pub const NFT_METADATA_SPEC: &str = "1.0.0";
@@ -32,9 +75,25 @@ pub const CERTY_CERT_VERSION: &str = "0.1.0";
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
+    //layout of this struct, used by `migrate` when new code is deployed. It comes first so it
+    //can be read before knowing the layout
+    pub state_version: StateVersion,
+
     //contract owner
     pub owner_id: AccountId,
 
+    //account proposed as the new contract owner, until it accepts the handover
+    pub pending_owner_id: Option<AccountId>,
+
+    //accounts appointed by the contract owner to administer the contract
+    pub admins: UnorderedSet<AccountId>,
+
+    //whether issuer operations are stopped contract-wide
+    pub paused: bool,
+
+    //categories whose issuer operations are stopped
+    pub frozen_categories: UnorderedSet<CategoryId>,
+
     pub count_token_id: u128,
 
     //keeps track of all the token IDs for a given account
@@ -60,6 +119,97 @@ pub struct Contract {
 
     //keeps track of the metadata for the contract
     pub metadata: LazyOption<NFTContractMetadata>,
+
+    //keeps track of the unacknowledged certificate changes for a given holder
+    pub cert_changes_per_holder: LookupMap<AccountId, UnorderedMap<TokenId, Vec<CertChange>>>,
+
+    //keeps track of the endorsements by endorser for a given token ID
+    pub endorsements_per_token: LookupMap<TokenId, UnorderedMap<AccountId, Endorsement>>,
+
+    //accounts appointed by the contract owner to verify institutions
+    pub verifiers: UnorderedSet<AccountId>,
+
+    //keeps track of the verification for a given category owner
+    pub verified_issuers: UnorderedMap<AccountId, IssuerVerification>,
+
+    //accounts trusted by the contract owner to be the top of accreditation chains
+    pub root_accreditors: UnorderedSet<AccountId>,
+
+    //keeps track of the accreditation for a given accredited account
+    pub accreditations: LookupMap<AccountId, Accreditation>,
+
+    //keeps track of all the accounts accredited by a given accreditor
+    pub accredited_per_accreditor: LookupMap<AccountId, UnorderedSet<AccountId>>,
+
+    //keeps track of the NEP-145 storage balance available for a given account
+    pub storage_deposits: LookupMap<AccountId, Balance>,
+
+    //keeps track of the storage sponsorship left by sponsor for a given beneficiary
+    pub sponsorships_per_beneficiary: LookupMap<AccountId, UnorderedMap<AccountId, Balance>>,
+
+    //keeps track of the sponsor that paid the storage of a given token ID
+    pub storage_payer_by_token: LookupMap<TokenId, AccountId>,
+
+    //keeps track of the sponsor that paid the storage of a given category ID
+    pub storage_payer_by_category: LookupMap<CategoryId, AccountId>,
+
+    //protocol fees charged on issuance
+    pub fee_config: FeeConfig,
+
+    //protocol fees collected and not yet withdrawn by the contract owner
+    pub fees_collected: Balance,
+
+    //keeps track of the claim price of a given category ID
+    pub category_prices: LookupMap<CategoryId, CategoryPrice>,
+
+    //NEP-141 tokens claim prices can be set in
+    pub ft_whitelist: UnorderedSet<AccountId>,
+
+    //keeps track of all the token IDs an account holds from a given category
+    pub tokens_per_owner_category: LookupMap<(AccountId, CategoryId), UnorderedSet<TokenId>>,
+
+    //keeps track of all the token IDs ordered by the time they were issued
+    pub certs_by_issued_at: TreeMap<IssuanceKey, ()>,
+
+    //keeps track of the token IDs of a given category ordered by the time they were issued
+    pub certs_by_issued_at_per_category: LookupMap<CategoryId, TreeMap<IssuanceKey, ()>>,
+
+    //keeps track of the token IDs whose `media_hash` or `reference_hash` is a given document hash
+    pub tokens_per_document_hash: LookupMap<Vec<u8>, UnorderedSet<TokenId>>,
+
+    //keeps track of the index of a token in the status lists of its category
+    pub status_index_by_token: LookupMap<TokenId, u64>,
+
+    //keeps track of the number of indexes handed out in the status lists of a category
+    pub status_list_size_per_category: LookupMap<CategoryId, u64>,
+
+    //keeps track of the revocation and suspension bitstrings of a category
+    pub status_lists: LookupMap<(CategoryId, StatusPurpose), Vec<u8>>,
+
+    //keeps track of the suspension of a token while it is suspended
+    pub suspensions_by_token: LookupMap<TokenId, Suspension>,
+
+    //keeps track of the suspended tokens of a category
+    pub suspended_per_category: LookupMap<CategoryId, UnorderedSet<TokenId>>,
+
+    //keeps track of every appeal against the revocations of a token, oldest first
+    pub appeals_per_token: LookupMap<TokenId, Vector<Appeal>>,
+
+    //keeps track of the account deciding appeals on the certificates of a category
+    pub arbiters_per_category: LookupMap<CategoryId, AccountId>,
+
+    //keeps track of the verification methods and services of the DID document of an account
+    pub did_records: LookupMap<AccountId, DidRecord>,
+
+    //keeps track of the ed25519 keys an issuer signs certificates with off-chain
+    pub issuer_keys: LookupMap<AccountId, Vec<IssuerKey>>,
+
+    //keeps track of the NEP-141 claim payouts that failed to transfer, by account and token contract
+    pub ft_payouts: LookupMap<(AccountId, AccountId), Balance>,
+
+    //URL the status list credentials are served under, none for the did:near path
+    pub credential_base_url: Option<String>,
+
 }
 
 /// Helper structure for keys of the persistent collections.
@@ -79,6 +229,44 @@ pub enum StorageKey {
     TokensPerType,
     TokensPerTypeInner { token_type_hash: CryptoHash },
     TokenTypesLocked,
+    CertChangesPerHolder,
+    CertChangesPerHolderInner { account_id_hash: CryptoHash },
+    EndorsementsPerToken,
+    EndorsementsPerTokenInner { token_id_hash: CryptoHash },
+    Verifiers,
+    VerifiedIssuers,
+    RootAccreditors,
+    Accreditations,
+    AccreditedPerAccreditor,
+    AccreditedPerAccreditorInner { account_id_hash: CryptoHash },
+    Admins,
+    FrozenCategories,
+    StorageDeposits,
+    SponsorshipsPerBeneficiary,
+    SponsorshipsPerBeneficiaryInner { account_id_hash: CryptoHash },
+    StoragePayerByToken,
+    StoragePayerByCategory,
+    CategoryPrices,
+    FtWhitelist,
+    TokensPerOwnerCategory,
+    TokensPerOwnerCategoryInner { owner_category_hash: CryptoHash },
+    CertsByIssuedAt,
+    CertsByIssuedAtPerCategory,
+    CertsByIssuedAtPerCategoryInner { category_id_hash: CryptoHash },
+    TokensPerDocumentHash,
+    TokensPerDocumentHashInner { document_hash_hash: CryptoHash },
+    StatusIndexByToken,
+    StatusListSizePerCategory,
+    StatusLists,
+    SuspensionsByToken,
+    ArbitersPerCategory,
+    DidRecords,
+    IssuerKeys,
+    FtPayouts,
+    AppealsPerToken,
+    AppealsPerTokenInner { token_id_hash: CryptoHash },
+    SuspendedPerCategory,
+    SuspendedPerCategoryInner { category_id_hash: CryptoHash },
 }
 
 #[near_bindgen]
@@ -102,7 +290,8 @@ impl Contract {
 
     #[init]
     pub fn new(owner_id: AccountId, metadata: NFTContractMetadata) -> Self {
-        let this = Self {
+        Self {
+            state_version: StateVersion::V1,
             tokens_per_owner: LookupMap::new(StorageKey::TokensPerOwner.try_to_vec().unwrap()),
             categories_per_owner: LookupMap::new(
                 StorageKey::CategoriesPerOwner.try_to_vec().unwrap(),
@@ -119,12 +308,75 @@ impl Contract {
                 StorageKey::CategoryMetadataById.try_to_vec().unwrap(),
             ),
             owner_id,
+            pending_owner_id: None,
+            admins: UnorderedSet::new(StorageKey::Admins.try_to_vec().unwrap()),
+            paused: false,
+            frozen_categories: UnorderedSet::new(
+                StorageKey::FrozenCategories.try_to_vec().unwrap(),
+            ),
             metadata: LazyOption::new(
                 StorageKey::NFTContractMetadata.try_to_vec().unwrap(),
                 Some(&metadata),
             ),
             count_token_id: 0,
-        };
-        this
+            cert_changes_per_holder: LookupMap::new(
+                StorageKey::CertChangesPerHolder.try_to_vec().unwrap(),
+            ),
+            endorsements_per_token: LookupMap::new(
+                StorageKey::EndorsementsPerToken.try_to_vec().unwrap(),
+            ),
+            verifiers: UnorderedSet::new(StorageKey::Verifiers.try_to_vec().unwrap()),
+            verified_issuers: UnorderedMap::new(StorageKey::VerifiedIssuers.try_to_vec().unwrap()),
+            root_accreditors: UnorderedSet::new(StorageKey::RootAccreditors.try_to_vec().unwrap()),
+            accreditations: LookupMap::new(StorageKey::Accreditations.try_to_vec().unwrap()),
+            accredited_per_accreditor: LookupMap::new(
+                StorageKey::AccreditedPerAccreditor.try_to_vec().unwrap(),
+            ),
+            storage_deposits: LookupMap::new(StorageKey::StorageDeposits.try_to_vec().unwrap()),
+            sponsorships_per_beneficiary: LookupMap::new(
+                StorageKey::SponsorshipsPerBeneficiary.try_to_vec().unwrap(),
+            ),
+            storage_payer_by_token: LookupMap::new(
+                StorageKey::StoragePayerByToken.try_to_vec().unwrap(),
+            ),
+            storage_payer_by_category: LookupMap::new(
+                StorageKey::StoragePayerByCategory.try_to_vec().unwrap(),
+            ),
+            fee_config: FeeConfig::default(),
+            fees_collected: 0,
+            category_prices: LookupMap::new(StorageKey::CategoryPrices.try_to_vec().unwrap()),
+            ft_whitelist: UnorderedSet::new(StorageKey::FtWhitelist.try_to_vec().unwrap()),
+            tokens_per_owner_category: LookupMap::new(
+                StorageKey::TokensPerOwnerCategory.try_to_vec().unwrap(),
+            ),
+            certs_by_issued_at: TreeMap::new(StorageKey::CertsByIssuedAt.try_to_vec().unwrap()),
+            certs_by_issued_at_per_category: LookupMap::new(
+                StorageKey::CertsByIssuedAtPerCategory.try_to_vec().unwrap(),
+            ),
+            tokens_per_document_hash: LookupMap::new(
+                StorageKey::TokensPerDocumentHash.try_to_vec().unwrap(),
+            ),
+            status_index_by_token: LookupMap::new(
+                StorageKey::StatusIndexByToken.try_to_vec().unwrap(),
+            ),
+            status_list_size_per_category: LookupMap::new(
+                StorageKey::StatusListSizePerCategory.try_to_vec().unwrap(),
+            ),
+            status_lists: LookupMap::new(StorageKey::StatusLists.try_to_vec().unwrap()),
+            suspensions_by_token: LookupMap::new(
+                StorageKey::SuspensionsByToken.try_to_vec().unwrap(),
+            ),
+            suspended_per_category: LookupMap::new(
+                StorageKey::SuspendedPerCategory.try_to_vec().unwrap(),
+            ),
+            appeals_per_token: LookupMap::new(StorageKey::AppealsPerToken.try_to_vec().unwrap()),
+            arbiters_per_category: LookupMap::new(
+                StorageKey::ArbitersPerCategory.try_to_vec().unwrap(),
+            ),
+            did_records: LookupMap::new(StorageKey::DidRecords.try_to_vec().unwrap()),
+            issuer_keys: LookupMap::new(StorageKey::IssuerKeys.try_to_vec().unwrap()),
+            ft_payouts: LookupMap::new(StorageKey::FtPayouts.try_to_vec().unwrap()),
+            credential_base_url: None,
+        }
     }
 }
@@ -5,16 +5,36 @@ impl Contract {
     #[payable]
     pub fn category_create(&mut self, category_id: CategoryId, metadata: CategoryMetadata) {
         assert_at_least_one_yocto();
+        self.assert_not_paused();
         let initial_storage_usage = env::storage_usage();
-        self.internal_category_create(category_id, env::predecessor_account_id(), metadata);
-        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
-
-        refund_deposit(required_storage_in_bytes);
+        self.internal_category_create(category_id.clone(), env::predecessor_account_id(), metadata);
+        let payer_id = self.internal_storage_payer(
+            &env::predecessor_account_id(),
+            env::storage_usage() - initial_storage_usage + STORAGE_PAYER_ENTRY_BYTES,
+        );
+        if payer_id != env::predecessor_account_id() {
+            self.storage_payer_by_category
+                .insert(&category_id, &payer_id);
+        }
+        let attached_deposit = self.internal_fee_collect(
+            &env::predecessor_account_id(),
+            self.fee_config.category_create_fee,
+            None,
+            Some(vec![category_id]),
+        );
+        self.internal_storage_charge(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+            attached_deposit,
+        );
     }
     #[payable]
     pub fn category_update(&mut self, category_id: CategoryId, metadata: CategoryMetadata) {
         assert_at_least_one_yocto();
+        self.assert_not_paused();
         self.assert_category_owner(env::predecessor_account_id(), &category_id);
+        self.assert_category_not_frozen(&category_id);
         let mut category_metadata = self.category_metadata_by_id.get(&category_id).unwrap();
         // updatable fields
         category_metadata.title = metadata.title;
@@ -27,15 +47,13 @@ impl Contract {
 
         let initial_storage_usage = env::storage_usage();
         self.internal_category_update(&category_id, &category_metadata);
-        let mut required_storage_in_bytes = 0;
-        if env::storage_usage() < initial_storage_usage {
-            let released_storage = initial_storage_usage - env::storage_usage();
-            Promise::new(env::predecessor_account_id())
-                .transfer(Balance::from(released_storage) * env::storage_byte_cost());
-        } else {
-            required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
-        }
-        refund_deposit(required_storage_in_bytes);
+        //changes in size are settled with whoever paid for the category
+        let payer_id = self.category_storage_payer(category_id).unwrap();
+        self.internal_storage_adjust(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+        );
     }
     // pub fn category_transfer_ownership(
     //     &mut self,
@@ -46,14 +64,18 @@ impl Contract {
     // }
     #[payable]
     pub fn category_delete(&mut self, category_id: CategoryId) {
+        self.assert_not_paused();
         self.assert_category_owner(env::predecessor_account_id(), &category_id);
+        self.assert_category_not_frozen(&category_id);
+        let payer_id = self.category_storage_payer(category_id.clone()).unwrap();
         let initial_storage_usage = env::storage_usage();
         self.internal_category_delete(category_id);
-        if env::storage_usage() < initial_storage_usage {
-            let released_storage = initial_storage_usage - env::storage_usage();
-            Promise::new(env::predecessor_account_id())
-                .transfer(Balance::from(released_storage) * env::storage_byte_cost());
-        } 
+        //freed storage goes back to whoever paid for the category
+        self.internal_storage_release(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+        );
     }
     //Query for all the categories of an owner
     pub fn categories_for_owner(
@@ -62,27 +84,31 @@ impl Contract {
         from_index: Option<U128>,
         limit: Option<u64>,
     ) -> Vec<JsonCategory> {
+        self.categories_for_owner_page(account_id, from_index, limit)
+            .items
+    }
+    pub fn categories_for_owner_page(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Page<JsonCategory> {
         let categories_for_owner_set = self.categories_per_owner.get(&account_id);
         let categories = if let Some(categories_for_owner_set) = categories_for_owner_set {
             categories_for_owner_set
         } else {
-            return vec![];
+            return Page::empty();
         };
 
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        categories
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(50) as usize)
-            .map(|category_id| self.category_info(category_id.clone()).unwrap())
-            .collect()
+        paginate(categories.as_vector(), from_index, limit)
+            .map(|category_id| self.category_info(category_id).unwrap())
     }
     pub fn category_info(&self, category_id: CategoryId) -> Option<JsonCategory> {
         if let Some(category) = self.categories_by_id.get(&category_id) {
             let metadata = self.category_metadata_by_id.get(&category_id).unwrap();
             Some(JsonCategory {
                 category_id,
+                verified: self.verified_issuers.get(&category.owner_id).is_some(),
                 owner_id: category.owner_id,
                 metadata,
             })
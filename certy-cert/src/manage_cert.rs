@@ -10,11 +10,30 @@ impl Contract {
         category_id: CategoryId,
     ) {
         assert_at_least_one_yocto();
+        self.assert_not_paused();
         self.assert_category_owner(env::predecessor_account_id(), &category_id);
+        self.assert_category_not_frozen(&category_id);
         let initial_storage_usage = env::storage_usage();
-        self.internal_mint_token(receiver_id, metadata, category_id);
-        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
-        refund_deposit(required_storage_in_bytes);
+        let token_id = self.internal_mint_token(receiver_id, metadata, category_id);
+        let payer_id = self.internal_storage_payer(
+            &env::predecessor_account_id(),
+            env::storage_usage() - initial_storage_usage + STORAGE_PAYER_ENTRY_BYTES,
+        );
+        if payer_id != env::predecessor_account_id() {
+            self.storage_payer_by_token.insert(&token_id, &payer_id);
+        }
+        let attached_deposit = self.internal_fee_collect(
+            &env::predecessor_account_id(),
+            self.fee_config.mint_fee,
+            Some(vec![token_id]),
+            None,
+        );
+        self.internal_storage_charge(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+            attached_deposit,
+        );
     }
     #[payable]
     pub fn nft_bulk_mint(
@@ -24,27 +43,57 @@ impl Contract {
         category_id: CategoryId,
     ) {
         assert_at_least_one_yocto();
+        self.assert_not_paused();
         self.assert_category_owner(env::predecessor_account_id(), &category_id);
+        self.assert_category_not_frozen(&category_id);
         assert_eq!(
             metadatas.len(),
             receiver_ids.len(),
             "Metadatas and receiver_ids must be the same length"
         );
         let initial_storage_usage = env::storage_usage();
+        let mut token_ids = vec![];
         for (pos, receiver_id) in receiver_ids.iter().enumerate() {
-            self.internal_mint_token(
+            token_ids.push(self.internal_mint_token(
                 receiver_id.clone(),
                 metadatas[pos].clone(),
                 category_id.clone(),
-            );
+            ));
+        }
+        let payer_id = self.internal_storage_payer(
+            &env::predecessor_account_id(),
+            env::storage_usage() - initial_storage_usage
+                + STORAGE_PAYER_ENTRY_BYTES * token_ids.len() as u64,
+        );
+        if payer_id != env::predecessor_account_id() {
+            for token_id in token_ids.iter() {
+                self.storage_payer_by_token.insert(token_id, &payer_id);
+            }
         }
-        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
-        refund_deposit(required_storage_in_bytes);
+        let attached_deposit = self.internal_fee_collect(
+            &env::predecessor_account_id(),
+            self.fee_config.mint_fee,
+            Some(token_ids),
+            None,
+        );
+        self.internal_storage_charge(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+            attached_deposit,
+        );
     }
     #[payable]
-    pub fn cert_update(&mut self, metadata: TokenMetadata, token_id: TokenId) {
+    pub fn cert_update(
+        &mut self,
+        metadata: TokenMetadata,
+        token_id: TokenId,
+        reason: Option<String>,
+    ) {
         assert_at_least_one_yocto();
+        self.assert_not_paused();
         self.assert_cert_provider(env::predecessor_account_id(), &token_id);
+        self.assert_token_not_frozen(&token_id);
         let initial_storage_usage = env::storage_usage();
         let mut cert_metadata = self.token_metadata_by_id.get(&token_id).unwrap();
         // updatable fields
@@ -58,34 +107,36 @@ impl Contract {
         cert_metadata.reference = metadata.reference;
         cert_metadata.reference_hash = metadata.reference_hash;
 
-        self.internal_token_update(&token_id, &cert_metadata);
-        let mut required_storage_in_bytes = 0;
-        if env::storage_usage() < initial_storage_usage {
-            let released_storage = initial_storage_usage - env::storage_usage();
-            Promise::new(env::predecessor_account_id())
-                .transfer(Balance::from(released_storage) * env::storage_byte_cost());
-        } else {
-            required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
-        }
-        refund_deposit(required_storage_in_bytes);
+        self.internal_token_update(&token_id, &cert_metadata, reason);
+        //changes in size are settled with whoever paid for the certificate
+        let payer_id = self.cert_storage_payer(token_id).unwrap();
+        self.internal_storage_adjust(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+        );
     }
     #[payable]
     pub fn cert_delete(&mut self, token_id: TokenId) {
         assert_one_yocto();
+        self.assert_not_paused();
         self.assert_cert_provider(env::predecessor_account_id(), &token_id);
+        self.assert_token_not_frozen(&token_id);
+        let payer_id = self.cert_storage_payer(token_id.clone()).unwrap();
+        //endorsements can't outlive the certificate they vouch for
+        self.internal_endorsements_release(&token_id);
+        //nor can the appeals of its holder
+        self.internal_appeals_release(&token_id);
         let initial_storage_usage = env::storage_usage();
 
         self.internal_token_delete(token_id);
 
-        let mut required_storage_in_bytes = 0;
-        if env::storage_usage() < initial_storage_usage {
-            let released_storage = initial_storage_usage - env::storage_usage();
-            Promise::new(env::predecessor_account_id())
-                .transfer(Balance::from(released_storage) * env::storage_byte_cost());
-        } else {
-            required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
-        }
-        refund_deposit(required_storage_in_bytes);
+        //freed storage goes back to whoever paid for the certificate
+        self.internal_storage_release(
+            &payer_id,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+        );
     }
     //Cert by category
     pub fn cert_get_by_category(
@@ -94,20 +145,23 @@ impl Contract {
         from_index: Option<U128>,
         limit: Option<u64>,
     ) -> Vec<JsonToken> {
+        self.cert_get_by_category_page(category_id, from_index, limit)
+            .items
+    }
+    pub fn cert_get_by_category_page(
+        &self,
+        category_id: CategoryId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Page<JsonToken> {
         let tokens_of_category_set = self.tokens_per_category.get(&category_id);
         let tokens = if let Some(tokens_of_category_set) = tokens_of_category_set {
             tokens_of_category_set
         } else {
-            return vec![];
+            return Page::empty();
         };
 
-        let start = u128::from(from_index.unwrap_or(U128(0)));
-
-        tokens
-            .iter()
-            .skip(start as usize)
-            .take(limit.unwrap_or(50) as usize)
-            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
-            .collect()
+        paginate(tokens.as_vector(), from_index, limit)
+            .map(|token_id| self.nft_token(token_id).unwrap())
     }
 }
@@ -1,7 +1,7 @@
 use crate::*;
 pub type TokenId = String;
 
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct NFTContractMetadata {
     pub spec: String,              // required, essentially a version like "nft-1.0.0"
@@ -56,6 +56,47 @@ pub struct JsonToken {
     pub owner_id: AccountId,
     pub category_id: CategoryId,
     pub metadata: TokenMetadata,
+    //whether the owner of the category that issued the token is a verified institution
+    pub verified: bool,
+}
+
+//A token along with its status now, for views that list revoked and suspended certificates too
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonTokenWithStatus {
+    #[serde(flatten)]
+    pub token: JsonToken,
+    pub status: CertStatus,
+}
+
+//Status of a certificate derived from its validity dates
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum CertStatus {
+    //`starts_at` is still to come
+    NotStarted,
+    Active,
+    //`expires_at` is past
+    Expired,
+    //flagged in the suspension list of its category
+    Suspended,
+    //flagged in the revocation list of its category
+    Revoked,
+}
+
+impl CertStatus {
+    pub(crate) fn of(metadata: &TokenMetadata, now_ms: u64) -> Self {
+        let starts_at = metadata.starts_at.unwrap_or(0);
+        let expires_at = metadata.expires_at.unwrap_or(u64::MAX);
+        if starts_at > now_ms {
+            CertStatus::NotStarted
+        } else if expires_at <= now_ms {
+            CertStatus::Expired
+        } else {
+            CertStatus::Active
+        }
+    }
 }
 
 pub trait NonFungibleTokenMetadata {
@@ -1,3 +1,5 @@
+//the generated `ext_self` bindings append account, deposit and gas to every resolver argument
+#![allow(clippy::too_many_arguments)]
 use crate::*;
 use near_sdk::{ext_contract, PromiseResult};
 
@@ -28,7 +30,7 @@ trait NonFungibleTokenResolver {
     ) -> bool;
 }
 
-trait NonFungibleTokenResolver {
+pub trait NonFungibleTokenResolver {
     fn nft_resolve_transfer(
         &mut self,
         authorized_id: Option<String>,
@@ -44,11 +46,17 @@ impl NonFungibleTokenCore for Contract {
     fn nft_token(&self, token_id: TokenId) -> Option<JsonToken> {
         if let Some(token) = self.tokens_by_id.get(&token_id) {
             let metadata = self.token_metadata_by_id.get(&token_id).unwrap();
+            let verified = self
+                .categories_by_id
+                .get(&token.category_id)
+                .map(|category| self.verified_issuers.get(&category.owner_id).is_some())
+                .unwrap_or(false);
             Some(JsonToken {
                 token_id,
                 owner_id: token.owner_id,
                 category_id: token.category_id,
                 metadata,
+                verified,
             })
         } else {
             None
@@ -101,6 +109,8 @@ impl NonFungibleTokenResolver for Contract {
         self.internal_token_remove_from_owner(&receiver_id.clone(), &token_id);
         //we add the token to the original owner
         self.internal_token_add_to_owner(&owner_id, &token_id);
+        self.internal_token_remove_from_owner_category(&receiver_id, &token.category_id, &token_id);
+        self.internal_token_add_to_owner_category(&owner_id, &token.category_id, &token_id);
 
         //we change the token struct's owner to be the original owner
         token.owner_id = owner_id.clone();
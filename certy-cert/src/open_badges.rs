@@ -0,0 +1,203 @@
+use crate::*;
+use near_sdk::serde_json;
+
+pub const OPEN_BADGES_CONTEXT_V3: &str = "https://purl.imsglobal.org/spec/ob/v3p0/context.json";
+
+fn alignment_type() -> Vec<String> {
+    vec!["Alignment".to_string()]
+}
+
+//Open Badges 3.0 `Profile` of an issuer
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BadgeProfile {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub profile_type: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+//How the achievement is earned, taken from `criteria` in the category `extra` JSON
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BadgeCriteria {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub narrative: Option<String>,
+}
+
+//Competency or standard the achievement aligns to, taken from `alignment` in the category `extra` JSON
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeAlignment {
+    #[serde(rename = "type", default = "alignment_type")]
+    pub alignment_type: Vec<String>,
+    pub target_name: String,
+    pub target_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_framework: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_code: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BadgeImage {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub image_type: String,
+}
+
+//Open Badges 3.0 `Achievement` rendering of a category
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Achievement {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub achievement_type: Vec<String>,
+    pub name: String,
+    pub description: String,
+    pub criteria: BadgeCriteria,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<BadgeImage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub alignment: Vec<BadgeAlignment>,
+    pub creator: BadgeProfile,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AchievementSubject {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub subject_type: Vec<String>,
+    pub achievement: Achievement,
+}
+
+//Open Badges 3.0 `OpenBadgeCredential` rendering of a certificate, unsigned:
+//the contract state is the proof
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct OpenBadgeCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<JsonLdContext>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub credential_type: Vec<String>,
+    pub name: String,
+    pub issuer: BadgeProfile,
+    pub issuance_date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_date: Option<String>,
+    pub credential_subject: AchievementSubject,
+    pub credential_status: Vec<CredentialStatus>,
+}
+
+#[near_bindgen]
+impl Contract {
+    //Render a category as an Open Badges 3.0 Achievement
+    pub fn category_achievement(&self, category_id: CategoryId) -> Option<Achievement> {
+        let category = self.category_info(category_id)?;
+        Some(self.internal_achievement(category))
+    }
+    //Render a certificate as an Open Badges 3.0 OpenBadgeCredential
+    pub fn cert_open_badge(&self, token_id: TokenId) -> Option<OpenBadgeCredential> {
+        let token = self.nft_token(token_id.clone())?;
+        let category = self.category_info(token.category_id.clone()).unwrap();
+        let issuer = self.internal_badge_profile(&category.owner_id);
+        let achievement = self.internal_achievement(category);
+        let contract_id = env::current_account_id();
+        let id = format!("{}/certs/{}", did_near(&contract_id), token_id);
+        let credential_status = self.internal_credential_status(&token.category_id, &token_id);
+
+        Some(OpenBadgeCredential {
+            context: credential_context(&[VC_CONTEXT_V1, OPEN_BADGES_CONTEXT_V3]),
+            id: id.clone(),
+            credential_type: vec![
+                "VerifiableCredential".to_string(),
+                "OpenBadgeCredential".to_string(),
+            ],
+            name: token
+                .metadata
+                .title
+                .unwrap_or_else(|| achievement.name.clone()),
+            issuer,
+            issuance_date: rfc3339_from_ms(token.metadata.issued_at.unwrap_or(0)),
+            expiration_date: token.metadata.expires_at.map(rfc3339_from_ms),
+            credential_subject: AchievementSubject {
+                id: did_near(&token.owner_id),
+                subject_type: vec!["AchievementSubject".to_string()],
+                achievement,
+            },
+            credential_status,
+        })
+    }
+}
+
+impl Contract {
+    //achievement of a category, criteria fall back to the category description.
+    //`criteria` and each `alignment` entry of the category `extra` JSON are read on their own,
+    //so a malformed one is left out without dropping the others
+    fn internal_achievement(&self, category: JsonCategory) -> Achievement {
+        let metadata = category.metadata;
+        let extra: serde_json::Value = metadata
+            .extra
+            .as_ref()
+            .and_then(|extra| serde_json::from_str(extra).ok())
+            .unwrap_or_default();
+        let criteria: Option<BadgeCriteria> = extra
+            .get("criteria")
+            .and_then(|criteria| serde_json::from_value(criteria.clone()).ok());
+        let alignment: Vec<BadgeAlignment> = extra
+            .get("alignment")
+            .and_then(|alignment| alignment.as_array())
+            .map(|alignment| {
+                alignment
+                    .iter()
+                    .filter_map(|entry| serde_json::from_value(entry.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let description = metadata.description.unwrap_or_default();
+        Achievement {
+            id: format!(
+                "{}/categories/{}",
+                did_near(&env::current_account_id()),
+                category.category_id
+            ),
+            achievement_type: vec!["Achievement".to_string()],
+            name: metadata.title.unwrap_or(category.category_id),
+            criteria: criteria.unwrap_or_else(|| BadgeCriteria {
+                id: None,
+                narrative: Some(description.clone()).filter(|narrative| !narrative.is_empty()),
+            }),
+            description,
+            image: metadata.media.map(|media| BadgeImage {
+                id: media,
+                image_type: "Image".to_string(),
+            }),
+            alignment,
+            creator: self.internal_badge_profile(&category.owner_id),
+        }
+    }
+    //issuer profile of an account, named after the verified institution
+    fn internal_badge_profile(&self, account_id: &AccountId) -> BadgeProfile {
+        let verification = self.verified_issuers.get(account_id);
+        BadgeProfile {
+            id: did_near(account_id),
+            profile_type: vec!["Profile".to_string()],
+            name: verification
+                .as_ref()
+                .map(|verification| verification.display_name.clone()),
+            url: verification.map(|verification| format!("https://{}", verification.domain)),
+        }
+    }
+}
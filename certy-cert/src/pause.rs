@@ -0,0 +1,101 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //Emergency stop of every issuer operation, view methods keep working
+    #[payable]
+    pub fn contract_pause(&mut self) {
+        assert_one_yocto();
+        self.assert_admin();
+        assert!(!self.paused, "Contract already paused");
+        self.paused = true;
+        let contract_pause_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::ContractPause(vec![ContractPauseLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+            }]),
+        };
+        contract_pause_log.emit();
+    }
+    #[payable]
+    pub fn contract_unpause(&mut self) {
+        assert_one_yocto();
+        self.assert_admin();
+        assert!(self.paused, "Contract not paused");
+        self.paused = false;
+        let contract_unpause_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::ContractUnpause(vec![ContractPauseLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+            }]),
+        };
+        contract_unpause_log.emit();
+    }
+    //Stop every operation on a single category and its certificates
+    #[payable]
+    pub fn category_freeze(&mut self, category_id: CategoryId, reason: Option<String>) {
+        assert_at_least_one_yocto();
+        self.assert_admin();
+        assert!(
+            self.categories_by_id.contains_key(&category_id),
+            "No Category"
+        );
+        let initial_storage_usage = env::storage_usage();
+        assert!(
+            self.frozen_categories.insert(&category_id),
+            "Category already frozen"
+        );
+        let category_freeze_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::CategoryFreeze(vec![CategoryFreezeLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                category_ids: vec![category_id.to_string()],
+                reason,
+            }]),
+        };
+        category_freeze_log.emit();
+        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
+        refund_deposit(required_storage_in_bytes);
+    }
+    #[payable]
+    pub fn category_unfreeze(&mut self, category_id: CategoryId) {
+        assert_one_yocto();
+        self.assert_admin();
+        let initial_storage_usage = env::storage_usage();
+        assert!(
+            self.frozen_categories.remove(&category_id),
+            "Category not frozen"
+        );
+        let category_unfreeze_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::CategoryUnfreeze(vec![CategoryFreezeLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                category_ids: vec![category_id.to_string()],
+                reason: None,
+            }]),
+        };
+        category_unfreeze_log.emit();
+        if env::storage_usage() < initial_storage_usage {
+            let released_storage = initial_storage_usage - env::storage_usage();
+            Promise::new(env::predecessor_account_id())
+                .transfer(Balance::from(released_storage) * env::storage_byte_cost());
+        }
+    }
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+    pub fn is_category_frozen(&self, category_id: CategoryId) -> bool {
+        self.frozen_categories.contains(&category_id)
+    }
+    pub fn frozen_categories(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<CategoryId> {
+        paginate(self.frozen_categories.as_vector(), from_index, limit).items
+    }
+}
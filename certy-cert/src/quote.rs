@@ -0,0 +1,553 @@
+use crate::*;
+use std::collections::HashSet;
+
+//bytes the runtime charges for every stored record on top of its key and value
+pub const STORAGE_RECORD_OVERHEAD_BYTES: u64 = 40;
+
+//Storage estimate of a call, `deposit` is the yoctoNEAR to attach when the caller pays for it
+//and includes the protocol `fee`
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageQuote {
+    pub bytes: U64,
+    pub fee: U128,
+    pub deposit: U128,
+}
+
+impl StorageQuote {
+    pub(crate) fn new(bytes: u64, fee: Balance) -> Self {
+        StorageQuote {
+            bytes: U64(bytes),
+            fee: U128(fee),
+            deposit: U128(Balance::from(bytes) * env::storage_byte_cost() + fee),
+        }
+    }
+}
+
+//borsh serialized length of a value, as written to storage
+pub(crate) fn borsh_len<T: BorshSerialize>(value: &T) -> u64 {
+    value.try_to_vec().unwrap().len() as u64
+}
+
+//bytes of a new `LookupMap` entry: a single record under `prefix + key`
+pub(crate) fn lookup_map_entry_bytes(prefix_len: u64, key_len: u64, value_len: u64) -> u64 {
+    STORAGE_RECORD_OVERHEAD_BYTES + prefix_len + key_len + value_len
+}
+
+//bytes of a new `UnorderedMap` entry: the key index plus an element in the keys and values vectors
+pub(crate) fn unordered_map_entry_bytes(prefix_len: u64, key_len: u64, value_len: u64) -> u64 {
+    lookup_map_entry_bytes(prefix_len + 1, key_len, 8)
+        + lookup_map_entry_bytes(prefix_len + 1, 8, key_len)
+        + lookup_map_entry_bytes(prefix_len + 1, 8, value_len)
+}
+
+//bytes of a new `UnorderedSet` element: the element index plus an element in the elements vector
+pub(crate) fn unordered_set_entry_bytes(prefix_len: u64, element_len: u64) -> u64 {
+    lookup_map_entry_bytes(prefix_len + 1, element_len, 8)
+        + lookup_map_entry_bytes(prefix_len + 1, 8, element_len)
+}
+
+//bytes of a new `TreeMap` entry: the value record plus a tree node, and the edge linking the
+//node to its parent when the tree isn't empty
+pub(crate) fn tree_map_entry_bytes(
+    prefix_len: u64,
+    key_len: u64,
+    value_len: u64,
+    tree_empty: bool,
+) -> u64 {
+    //a node is its id, key, left and right children and height
+    let node_len = 8 + key_len + 1 + 1 + 8;
+    let edge_len = if tree_empty { 0 } else { 8 };
+    lookup_map_entry_bytes(prefix_len + 1, key_len, value_len)
+        + lookup_map_entry_bytes(prefix_len + 1, 8, node_len)
+        + edge_len
+}
+
+#[near_bindgen]
+impl Contract {
+    //Estimate the storage `nft_mint` takes for the given payload
+    pub fn nft_mint_quote(
+        &self,
+        metadata: TokenMetadata,
+        receiver_id: AccountId,
+        category_id: CategoryId,
+    ) -> StorageQuote {
+        self.nft_bulk_mint_quote(vec![metadata], vec![receiver_id], category_id)
+    }
+    //Estimate the storage `nft_bulk_mint` takes for the given payload
+    pub fn nft_bulk_mint_quote(
+        &self,
+        metadatas: Vec<TokenMetadata>,
+        receiver_ids: Vec<AccountId>,
+        category_id: CategoryId,
+    ) -> StorageQuote {
+        assert_eq!(
+            metadatas.len(),
+            receiver_ids.len(),
+            "Metadatas and receiver_ids must be the same length"
+        );
+        //owner sets created by an earlier token of the same call
+        let mut new_owner_sets = HashSet::new();
+        //document hash sets created by an earlier token of the same call
+        let mut new_document_hash_sets = HashSet::new();
+        let status_list_size = self
+            .status_list_size_per_category
+            .get(&category_id)
+            .unwrap_or(0);
+        let mut bytes = 0;
+        for (pos, receiver_id) in receiver_ids.iter().enumerate() {
+            let token_id: TokenId = (self.count_token_id + pos as u128).to_string();
+            bytes += self.internal_mint_quote_bytes(
+                receiver_id,
+                &metadatas[pos],
+                &category_id,
+                &token_id,
+                !new_owner_sets.insert(receiver_id.clone()),
+                pos > 0,
+                &mut new_document_hash_sets,
+            );
+            //the category set and feed only get created by the first token
+            if pos == 0
+                && !self
+                    .certs_by_issued_at_per_category
+                    .contains_key(&category_id)
+            {
+                bytes += lookup_map_entry_bytes(
+                    borsh_len(&StorageKey::CertsByIssuedAtPerCategory),
+                    borsh_len(&category_id),
+                    borsh_len(&TreeMap::<IssuanceKey, ()>::new(
+                        StorageKey::CertsByIssuedAtPerCategoryInner {
+                            category_id_hash: hash_category_id(&category_id),
+                        }
+                        .try_to_vec()
+                        .unwrap(),
+                    )),
+                );
+            }
+            if pos == 0 && !self.tokens_per_category.contains_key(&category_id) {
+                bytes += lookup_map_entry_bytes(
+                    borsh_len(&StorageKey::TokensPerCategory),
+                    borsh_len(&category_id),
+                    borsh_len(&UnorderedSet::<TokenId>::new(
+                        StorageKey::TokenPerCategoryInner {
+                            category_id_hash: hash_category_id(&category_id),
+                        }
+                        .try_to_vec()
+                        .unwrap(),
+                    )),
+                );
+            }
+            //every eighth certificate of a category grows its status lists by a byte
+            let status_index = status_list_size + pos as u64;
+            if status_index == 0 {
+                bytes += lookup_map_entry_bytes(
+                    borsh_len(&StorageKey::StatusListSizePerCategory),
+                    borsh_len(&category_id),
+                    8,
+                );
+            }
+            if status_index % 8 == 0 {
+                for purpose in [StatusPurpose::Revocation, StatusPurpose::Suspension].iter() {
+                    bytes += if status_index == 0 {
+                        lookup_map_entry_bytes(
+                            borsh_len(&StorageKey::StatusLists),
+                            borsh_len(&(category_id.clone(), *purpose)),
+                            borsh_len(&vec![0u8]),
+                        )
+                    } else {
+                        1
+                    };
+                }
+            }
+        }
+        //the category owner is the only one minting
+        let fee = self
+            .categories_by_id
+            .get(&category_id)
+            .map(|category| self.mint_fee_for(category.owner_id).0)
+            .unwrap_or(self.fee_config.mint_fee);
+        StorageQuote::new(bytes, fee * receiver_ids.len() as u128)
+    }
+    //Estimate the storage `category_create` takes for the given payload
+    pub fn category_create_quote(
+        &self,
+        account_id: AccountId,
+        category_id: CategoryId,
+        metadata: CategoryMetadata,
+    ) -> StorageQuote {
+        let category = Category {
+            owner_id: account_id.clone(),
+        };
+        let mut category_metadata = metadata;
+        category_metadata.issued_at = Some(env::block_timestamp_ms());
+        category_metadata.updated_at = Some(env::block_timestamp_ms());
+        let category_id_len = borsh_len(&category_id);
+
+        let mut bytes = lookup_map_entry_bytes(
+            borsh_len(&StorageKey::CategoriesById),
+            category_id_len,
+            borsh_len(&category),
+        ) + unordered_map_entry_bytes(
+            borsh_len(&StorageKey::CategoryMetadataById),
+            category_id_len,
+            borsh_len(&category_metadata),
+        );
+        let inner_prefix = StorageKey::CategoryPerOwnerInner {
+            account_id_hash: hash_account_id(&account_id),
+        }
+        .try_to_vec()
+        .unwrap();
+        if !self.categories_per_owner.contains_key(&account_id) {
+            bytes += lookup_map_entry_bytes(
+                borsh_len(&StorageKey::CategoriesPerOwner),
+                borsh_len(&account_id),
+                borsh_len(&UnorderedSet::<CategoryId>::new(inner_prefix.clone())),
+            );
+        }
+        bytes += unordered_set_entry_bytes(inner_prefix.len() as u64, category_id_len);
+        StorageQuote::new(bytes, self.category_create_fee_for(account_id).0)
+    }
+    //Estimate what `cert_claim` of a category takes from the claiming account,
+    //`fee` is the NEAR price attached on top of the storage deposit
+    pub fn cert_claim_quote(&self, account_id: AccountId, category_id: CategoryId) -> StorageQuote {
+        let price = self
+            .category_prices
+            .get(&category_id)
+            .expect("Category has no claim price");
+        assert!(price.ft_contract_id.is_none(), "Price is not in NEAR");
+        let category = self
+            .categories_by_id
+            .get(&category_id)
+            .expect("No Category");
+        let token_id: TokenId = self.count_token_id.to_string();
+        let mut bytes = self
+            .nft_mint_quote(price.metadata, account_id.clone(), category_id)
+            .bytes
+            .0;
+        //the claimer or its sponsor is recorded as the payer unless it is the category owner
+        if account_id != category.owner_id {
+            bytes += lookup_map_entry_bytes(
+                borsh_len(&StorageKey::StoragePayerByToken),
+                borsh_len(&token_id),
+                borsh_len(&account_id),
+            );
+        }
+        StorageQuote::new(bytes, price.amount)
+    }
+    //Estimate the storage `appeal_open` takes for the given payload
+    pub fn appeal_open_quote(
+        &self,
+        token_id: TokenId,
+        evidence_reference: String,
+        evidence_hash: Option<Base64VecU8>,
+    ) -> StorageQuote {
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+        let appeal = Appeal {
+            holder_id: token.owner_id,
+            evidence_reference,
+            evidence_hash,
+            opened_at: env::block_timestamp_ms(),
+            responses: vec![],
+            decision: None,
+        };
+        let appeals_prefix = StorageKey::AppealsPerTokenInner {
+            token_id_hash: hash_token_id(&token_id),
+        }
+        .try_to_vec()
+        .unwrap();
+        //appeals are stored by their u64 index in the vector
+        let mut bytes = lookup_map_entry_bytes(appeals_prefix.len() as u64, 8, borsh_len(&appeal));
+        if !self.appeals_per_token.contains_key(&token_id) {
+            bytes += lookup_map_entry_bytes(
+                borsh_len(&StorageKey::AppealsPerToken),
+                borsh_len(&token_id),
+                borsh_len(&Vector::<Appeal>::new(appeals_prefix)),
+            );
+        }
+        StorageQuote::new(bytes, 0)
+    }
+    //Estimate the storage `endorsement_add` by the account takes for the given payload,
+    //replacing an endorsement only costs the bytes it grows by
+    pub fn endorsement_add_quote(
+        &self,
+        account_id: AccountId,
+        token_id: TokenId,
+        comment: String,
+        reference: Option<String>,
+        reference_hash: Option<Base64VecU8>,
+    ) -> StorageQuote {
+        let endorsement = Endorsement {
+            comment,
+            reference,
+            reference_hash,
+            endorsed_at: env::block_timestamp_ms(),
+            hidden: false,
+        };
+        let endorsement_len = borsh_len(&endorsement);
+        let endorsements = self.endorsements_per_token.get(&token_id);
+        if let Some(old_endorsement) = endorsements
+            .as_ref()
+            .and_then(|endorsements| endorsements.get(&account_id))
+        {
+            return StorageQuote::new(
+                endorsement_len.saturating_sub(borsh_len(&old_endorsement)),
+                0,
+            );
+        }
+        let endorsements_prefix = StorageKey::EndorsementsPerTokenInner {
+            token_id_hash: hash_token_id(&token_id),
+        }
+        .try_to_vec()
+        .unwrap();
+        let mut bytes = unordered_map_entry_bytes(
+            endorsements_prefix.len() as u64,
+            borsh_len(&account_id),
+            endorsement_len,
+        );
+        if endorsements.is_none() {
+            bytes += lookup_map_entry_bytes(
+                borsh_len(&StorageKey::EndorsementsPerToken),
+                borsh_len(&token_id),
+                borsh_len(&UnorderedMap::<AccountId, Endorsement>::new(
+                    endorsements_prefix,
+                )),
+            );
+        }
+        StorageQuote::new(bytes, 0)
+    }
+}
+
+impl Contract {
+    //bytes written by `internal_mint_token` for a single token
+    #[allow(clippy::too_many_arguments)]
+    fn internal_mint_quote_bytes(
+        &self,
+        receiver_id: &AccountId,
+        metadata: &TokenMetadata,
+        category_id: &CategoryId,
+        token_id: &TokenId,
+        owner_set_created: bool,
+        //an earlier token of the same call already added to the feeds
+        feeds_created: bool,
+        new_document_hash_sets: &mut HashSet<Vec<u8>>,
+    ) -> u64 {
+        let token = Token {
+            owner_id: receiver_id.clone(),
+            category_id: category_id.clone(),
+        };
+        let mut cert_metadata = metadata.clone();
+        cert_metadata.issued_at = Some(env::block_timestamp_ms());
+        cert_metadata.updated_at = Some(env::block_timestamp_ms());
+        let token_id_len = borsh_len(token_id);
+
+        let mut bytes = lookup_map_entry_bytes(
+            borsh_len(&StorageKey::TokensById),
+            token_id_len,
+            borsh_len(&token),
+        ) + unordered_map_entry_bytes(
+            borsh_len(&StorageKey::TokenMetadataById),
+            token_id_len,
+            borsh_len(&cert_metadata),
+        );
+
+        let owner_prefix = StorageKey::TokenPerOwnerInner {
+            account_id_hash: hash_account_id(receiver_id),
+        }
+        .try_to_vec()
+        .unwrap();
+        if !owner_set_created && !self.tokens_per_owner.contains_key(receiver_id) {
+            bytes += lookup_map_entry_bytes(
+                borsh_len(&StorageKey::TokensPerOwner),
+                borsh_len(receiver_id),
+                borsh_len(&UnorderedSet::<TokenId>::new(owner_prefix.clone())),
+            );
+        }
+        bytes += unordered_set_entry_bytes(owner_prefix.len() as u64, token_id_len);
+
+        let category_prefix = StorageKey::TokenPerCategoryInner {
+            category_id_hash: hash_category_id(category_id),
+        }
+        .try_to_vec()
+        .unwrap();
+        bytes += unordered_set_entry_bytes(category_prefix.len() as u64, token_id_len);
+
+        let owner_category = (receiver_id.clone(), category_id.clone());
+        let owner_category_prefix = StorageKey::TokensPerOwnerCategoryInner {
+            owner_category_hash: hash_owner_category(receiver_id, category_id),
+        }
+        .try_to_vec()
+        .unwrap();
+        //every token of a call shares the category, so the owner alone tells if the set is new
+        if !owner_set_created && !self.tokens_per_owner_category.contains_key(&owner_category) {
+            bytes += lookup_map_entry_bytes(
+                borsh_len(&StorageKey::TokensPerOwnerCategory),
+                borsh_len(&owner_category),
+                borsh_len(&UnorderedSet::<TokenId>::new(owner_category_prefix.clone())),
+            );
+        }
+        bytes += unordered_set_entry_bytes(owner_category_prefix.len() as u64, token_id_len);
+
+        bytes +=
+            lookup_map_entry_bytes(borsh_len(&StorageKey::StatusIndexByToken), token_id_len, 8);
+
+        let issuance_key_len = borsh_len(&(env::block_timestamp_ms(), token_id.clone()));
+        bytes += tree_map_entry_bytes(
+            borsh_len(&StorageKey::CertsByIssuedAt),
+            issuance_key_len,
+            0,
+            !feeds_created && self.certs_by_issued_at.is_empty(),
+        );
+        let category_feed_prefix = StorageKey::CertsByIssuedAtPerCategoryInner {
+            category_id_hash: hash_category_id(category_id),
+        }
+        .try_to_vec()
+        .unwrap();
+        bytes += tree_map_entry_bytes(
+            category_feed_prefix.len() as u64,
+            issuance_key_len,
+            0,
+            !feeds_created
+                && !self
+                    .certs_by_issued_at_per_category
+                    .contains_key(category_id),
+        );
+
+        for document_hash in document_hashes(&cert_metadata) {
+            let document_hash_prefix = StorageKey::TokensPerDocumentHashInner {
+                document_hash_hash: hash_document_hash(&document_hash),
+            }
+            .try_to_vec()
+            .unwrap();
+            if !self.tokens_per_document_hash.contains_key(&document_hash)
+                && new_document_hash_sets.insert(document_hash.clone())
+            {
+                bytes += lookup_map_entry_bytes(
+                    borsh_len(&StorageKey::TokensPerDocumentHash),
+                    borsh_len(&document_hash),
+                    borsh_len(&UnorderedSet::<TokenId>::new(document_hash_prefix.clone())),
+                );
+            }
+            bytes += unordered_set_entry_bytes(document_hash_prefix.len() as u64, token_id_len);
+        }
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{category_metadata, context, setup, token_metadata};
+    use near_sdk::test_utils::accounts;
+    use near_sdk::testing_env;
+
+    //bytes the storage grew by since `initial_storage_usage`
+    fn used_bytes(initial_storage_usage: u64) -> u64 {
+        env::storage_usage() - initial_storage_usage
+    }
+
+    #[test]
+    fn category_create_quote_covers_storage() {
+        let mut contract = setup();
+        for category_id in ["2", "a-much-longer-category-id"].iter() {
+            let quote = contract.category_create_quote(
+                accounts(1),
+                category_id.to_string(),
+                category_metadata(),
+            );
+            let initial_storage_usage = env::storage_usage();
+            contract.category_create(category_id.to_string(), category_metadata());
+            assert!(quote.bytes.0 >= used_bytes(initial_storage_usage));
+        }
+    }
+
+    #[test]
+    fn nft_mint_quote_covers_storage() {
+        let mut contract = setup();
+        let metadata = TokenMetadata {
+            media_hash: Some(Base64VecU8(env::sha256(b"certificate.pdf"))),
+            ..token_metadata()
+        };
+        //a new holder, then a holder who already has a certificate of the category
+        for receiver in [3, 2].iter() {
+            let quote =
+                contract.nft_mint_quote(metadata.clone(), accounts(*receiver), "1".to_string());
+            let initial_storage_usage = env::storage_usage();
+            contract.nft_mint(metadata.clone(), accounts(*receiver), "1".to_string());
+            assert!(quote.bytes.0 >= used_bytes(initial_storage_usage));
+        }
+    }
+
+    #[test]
+    fn nft_bulk_mint_quote_covers_storage() {
+        let mut contract = setup();
+        let receiver_ids = vec![accounts(3), accounts(3), accounts(4)];
+        let metadatas = vec![token_metadata(); 3];
+        let quote =
+            contract.nft_bulk_mint_quote(metadatas.clone(), receiver_ids.clone(), "1".to_string());
+        let initial_storage_usage = env::storage_usage();
+        contract.nft_bulk_mint(metadatas, receiver_ids, "1".to_string());
+        assert!(quote.bytes.0 >= used_bytes(initial_storage_usage));
+    }
+
+    #[test]
+    fn cert_claim_quote_covers_storage() {
+        let mut contract = setup();
+        contract.category_price_set(
+            "1".to_string(),
+            Some(JsonCategoryPrice {
+                ft_contract_id: None,
+                amount: U128(10u128.pow(24)),
+                metadata: token_metadata(),
+            }),
+        );
+        let quote = contract.cert_claim_quote(accounts(3), "1".to_string());
+        assert_eq!(quote.fee.0, 10u128.pow(24));
+        testing_env!(context(3, quote.deposit.0).build());
+        let initial_storage_usage = env::storage_usage();
+        contract.cert_claim("1".to_string());
+        assert!(quote.bytes.0 >= used_bytes(initial_storage_usage));
+    }
+
+    #[test]
+    fn appeal_open_quote_covers_storage() {
+        let mut contract = setup();
+        testing_env!(context(1, 1).build());
+        contract.cert_revoke("0".to_string(), None);
+        let evidence_reference = "https://example.com/evidence.pdf".to_string();
+        let evidence_hash = Some(Base64VecU8(env::sha256(b"evidence.pdf")));
+        let quote = contract.appeal_open_quote(
+            "0".to_string(),
+            evidence_reference.clone(),
+            evidence_hash.clone(),
+        );
+        testing_env!(context(2, quote.deposit.0).build());
+        let initial_storage_usage = env::storage_usage();
+        contract.appeal_open("0".to_string(), evidence_reference, evidence_hash);
+        assert!(quote.bytes.0 >= used_bytes(initial_storage_usage));
+    }
+
+    #[test]
+    fn endorsement_add_quote_covers_storage() {
+        let mut contract = setup();
+        //a new endorsement, a second endorser, then a longer comment replacing the first one
+        for (endorser, comment) in [
+            (3, "Great work"),
+            (4, "Great work"),
+            (3, "Great work, really"),
+        ]
+        .iter()
+        {
+            let quote = contract.endorsement_add_quote(
+                accounts(*endorser),
+                "0".to_string(),
+                comment.to_string(),
+                None,
+                None,
+            );
+            testing_env!(context(*endorser, quote.deposit.0.max(1)).build());
+            let initial_storage_usage = env::storage_usage();
+            contract.endorsement_add("0".to_string(), comment.to_string(), None, None);
+            assert!(quote.bytes.0 >= used_bytes(initial_storage_usage));
+        }
+    }
+}
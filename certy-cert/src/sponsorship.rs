@@ -0,0 +1,84 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //Pre-fund the storage of the categories and certificates created by a beneficiary
+    #[payable]
+    pub fn sponsor_deposit(&mut self, beneficiary_id: AccountId) -> U128 {
+        let (amount, available) = self.internal_sponsor_deposit(&beneficiary_id);
+        let sponsor_deposit_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::SponsorDeposit(vec![SponsorLog {
+                sponsor_id: env::predecessor_account_id().to_string(),
+                beneficiary_id: beneficiary_id.to_string(),
+                amount: U128(amount),
+            }]),
+        };
+        sponsor_deposit_log.emit();
+        U128(available)
+    }
+    //Take back the unused sponsorship, all of it if no amount is given
+    #[payable]
+    pub fn sponsor_withdraw(&mut self, beneficiary_id: AccountId, amount: Option<U128>) -> U128 {
+        assert_one_yocto();
+        let (amount, available) = self.internal_sponsor_withdraw(&beneficiary_id, amount);
+        let sponsor_withdraw_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::SponsorWithdraw(vec![SponsorLog {
+                sponsor_id: env::predecessor_account_id().to_string(),
+                beneficiary_id: beneficiary_id.to_string(),
+                amount: U128(amount),
+            }]),
+        };
+        sponsor_withdraw_log.emit();
+        U128(available)
+    }
+    pub fn sponsorship_balance(&self, sponsor_id: AccountId, beneficiary_id: AccountId) -> U128 {
+        U128(self.internal_sponsorship_get(&sponsor_id, &beneficiary_id))
+    }
+    //Query for all the sponsorships of a beneficiary
+    pub fn sponsorships_for_beneficiary(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonSponsorship> {
+        let sponsorships_for_beneficiary = self.sponsorships_per_beneficiary.get(&account_id);
+        let sponsorships = if let Some(sponsorships_for_beneficiary) = sponsorships_for_beneficiary
+        {
+            sponsorships_for_beneficiary
+        } else {
+            return vec![];
+        };
+
+        paginate_map(&sponsorships, from_index, limit)
+            .items
+            .into_iter()
+            .map(|(sponsor_id, available)| JsonSponsorship {
+                sponsor_id,
+                beneficiary_id: account_id.clone(),
+                available: U128(available),
+            })
+            .collect()
+    }
+    //Account that paid the storage of a token
+    pub fn cert_storage_payer(&self, token_id: TokenId) -> Option<AccountId> {
+        let token = self.tokens_by_id.get(&token_id)?;
+        self.storage_payer_by_token.get(&token_id).or_else(|| {
+            self.categories_by_id
+                .get(&token.category_id)
+                .map(|category| category.owner_id)
+        })
+    }
+    //Account that paid the storage of a category
+    pub fn category_storage_payer(&self, category_id: CategoryId) -> Option<AccountId> {
+        let category = self.categories_by_id.get(&category_id)?;
+        Some(
+            self.storage_payer_by_category
+                .get(&category_id)
+                .unwrap_or(category.owner_id),
+        )
+    }
+}
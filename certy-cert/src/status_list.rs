@@ -0,0 +1,472 @@
+use crate::*;
+
+pub const STATUS_LIST_CONTEXT: &str = "https://w3id.org/vc/status-list/2021/v1";
+pub const STATUS_LIST_ENTRY_TYPE: &str = "StatusList2021Entry";
+//lists are published with at least this many entries so an index doesn't single out its holder
+pub const STATUS_LIST_MIN_BITS: u64 = 131_072;
+
+//What a set bit in a status list means
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, PartialEq, Clone, Copy, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum StatusPurpose {
+    Revocation,
+    Suspension,
+}
+
+impl StatusPurpose {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            StatusPurpose::Revocation => "revocation",
+            StatusPurpose::Suspension => "suspension",
+        }
+    }
+}
+
+//StatusList2021 credential publishing the status list of a category
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct StatusListCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub credential_type: Vec<String>,
+    pub issuer: String,
+    pub issuance_date: String,
+    pub credential_subject: StatusListSubject,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "camelCase")]
+pub struct StatusListSubject {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub subject_type: String,
+    pub status_purpose: StatusPurpose,
+    //base64url encoded gzip of the bitstring, the first certificate is the left-most bit
+    pub encoded_list: String,
+}
+
+//CRC-32 (IEEE) of the data, as stored in the gzip trailer
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+//writes deflate bits, least significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    bit_count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, len: u32) {
+        for i in 0..len {
+            if self.bit_count % 8 == 0 {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                *self.bytes.last_mut().unwrap() |= 1 << (self.bit_count % 8);
+            }
+            self.bit_count += 1;
+        }
+    }
+    //huffman codes are packed starting from their most significant bit
+    fn write_code(&mut self, code: u32, len: u32) {
+        let reversed = (0..len).fold(0, |acc, i| (acc << 1) | ((code >> i) & 1));
+        self.write(reversed, len);
+    }
+    //literal/length symbol with the fixed huffman code
+    fn write_symbol(&mut self, symbol: u32) {
+        match symbol {
+            0..=143 => self.write_code(0x30 + symbol, 8),
+            144..=255 => self.write_code(0x190 + symbol - 144, 9),
+            256..=279 => self.write_code(symbol - 256, 7),
+            _ => self.write_code(0xC0 + symbol - 280, 8),
+        }
+    }
+    //copy of the previous byte `len` times, 3 to 258
+    fn write_repeat(&mut self, len: u32) {
+        const BASES: [u32; 29] = [
+            3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99,
+            115, 131, 163, 195, 227, 258,
+        ];
+        let code = BASES.iter().rposition(|base| *base <= len).unwrap() as u32;
+        let extra_bits = if code < 8 || code == 28 {
+            0
+        } else {
+            code / 4 - 1
+        };
+        self.write_symbol(257 + code);
+        self.write(len - BASES[code as usize], extra_bits);
+        //distance 1 has the 5 bit code 0
+        self.write_code(0, 5);
+    }
+}
+
+//gzip of the data in a single fixed huffman block, runs of a byte are copies of the previous byte.
+//Status lists are mostly zero so runs are all it takes to shrink them
+pub(crate) fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter {
+        bytes: vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff],
+        bit_count: 80,
+    };
+    //final block, fixed huffman codes
+    writer.write(1, 1);
+    writer.write(1, 2);
+    let mut pos = 0;
+    while pos < data.len() {
+        let byte = data[pos];
+        writer.write_symbol(u32::from(byte));
+        let mut run = data[pos + 1..]
+            .iter()
+            .take_while(|next| **next == byte)
+            .count();
+        pos += 1 + run;
+        while run >= 3 {
+            let len = run.min(258);
+            writer.write_repeat(len as u32);
+            run -= len;
+        }
+        for _ in 0..run {
+            writer.write_symbol(u32::from(byte));
+        }
+    }
+    writer.write_symbol(256);
+    let mut bytes = writer.bytes;
+    bytes.extend_from_slice(&crc32(data).to_le_bytes());
+    bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    bytes
+}
+
+#[near_bindgen]
+impl Contract {
+    //Cert provider revokes a certificate for good, it stays with its holder flagged as revoked
+    #[payable]
+    pub fn cert_revoke(&mut self, token_id: TokenId, reason: Option<String>) {
+        assert_one_yocto();
+        self.assert_not_paused();
+        self.assert_cert_provider(env::predecessor_account_id(), &token_id);
+        self.assert_token_not_frozen(&token_id);
+        assert!(
+            !self.internal_status_get(&token_id, StatusPurpose::Revocation),
+            "Cert already revoked"
+        );
+        self.internal_status_set(&token_id, StatusPurpose::Revocation, true);
+        let cert_revoke_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::CertRevoke(vec![CertRevokeLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                token_ids: vec![token_id.to_string()],
+                reason,
+            }]),
+        };
+        cert_revoke_log.emit();
+    }
+    //Position of a certificate in the status lists of its category
+    pub fn cert_status_index(&self, token_id: TokenId) -> Option<U64> {
+        self.status_index_by_token.get(&token_id).map(U64)
+    }
+    //Status of a certificate now, revocation and suspension come before its validity dates
+    pub fn cert_status(&self, token_id: TokenId) -> Option<CertStatus> {
+        let metadata = self.token_metadata_by_id.get(&token_id)?;
+        Some(self.internal_cert_status(&token_id, &metadata, env::block_timestamp_ms()))
+    }
+    //StatusList2021 credential of a category, the `statusListCredential` of its certificates
+    pub fn category_status_list(
+        &self,
+        category_id: CategoryId,
+        purpose: StatusPurpose,
+    ) -> Option<StatusListCredential> {
+        let size = self.status_list_size_per_category.get(&category_id)?;
+        let mut bits = self
+            .status_lists
+            .get(&(category_id.clone(), purpose))
+            .unwrap();
+        bits.resize((size.max(STATUS_LIST_MIN_BITS) as usize + 7) / 8, 0);
+        if purpose == StatusPurpose::Suspension {
+            self.internal_clear_lifted_suspensions(&category_id, &mut bits);
+        }
+        let id = self.internal_status_list_url(&category_id, purpose);
+
+        Some(StatusListCredential {
+            context: vec![VC_CONTEXT_V1.to_string(), STATUS_LIST_CONTEXT.to_string()],
+            id: id.clone(),
+            credential_type: vec![
+                "VerifiableCredential".to_string(),
+                "StatusList2021Credential".to_string(),
+            ],
+            issuer: did_near(&env::current_account_id()),
+            issuance_date: rfc3339_from_ms(env::block_timestamp_ms()),
+            credential_subject: StatusListSubject {
+                id: format!("{}#list", id),
+                subject_type: "StatusList2021".to_string(),
+                status_purpose: purpose,
+                encoded_list: base64::encode_config(gzip(&bits), base64::URL_SAFE_NO_PAD),
+            },
+        })
+    }
+}
+
+impl Contract {
+    //give a token the next index of the status lists of its category, growing the lists to cover it
+    pub(crate) fn internal_status_index_assign(
+        &mut self,
+        category_id: &CategoryId,
+        token_id: &TokenId,
+    ) {
+        if self.status_index_by_token.contains_key(token_id) {
+            return;
+        }
+        let index = self
+            .status_list_size_per_category
+            .get(category_id)
+            .unwrap_or(0);
+        self.status_index_by_token.insert(token_id, &index);
+        self.status_list_size_per_category
+            .insert(category_id, &(index + 1));
+        if index % 8 == 0 {
+            for purpose in [StatusPurpose::Revocation, StatusPurpose::Suspension].iter() {
+                let key = (category_id.clone(), *purpose);
+                let mut bits = self.status_lists.get(&key).unwrap_or_default();
+                bits.push(0);
+                self.status_lists.insert(&key, &bits);
+            }
+        }
+    }
+    pub(crate) fn internal_status_get(&self, token_id: &TokenId, purpose: StatusPurpose) -> bool {
+        let token = self.tokens_by_id.get(token_id).expect("No token");
+        if let Some(index) = self.status_index_by_token.get(token_id) {
+            let bits = self
+                .status_lists
+                .get(&(token.category_id, purpose))
+                .unwrap();
+            bits[(index / 8) as usize] & (0x80 >> (index % 8)) != 0
+        } else {
+            false
+        }
+    }
+    //flip the bit of a token, the lists already cover every index so storage doesn't change
+    pub(crate) fn internal_status_set(
+        &mut self,
+        token_id: &TokenId,
+        purpose: StatusPurpose,
+        value: bool,
+    ) {
+        let token = self.tokens_by_id.get(token_id).expect("No token");
+        let index = self
+            .status_index_by_token
+            .get(token_id)
+            .expect("Cert has no status index, migrate the token indexes first");
+        let key = (token.category_id, purpose);
+        let mut bits = self.status_lists.get(&key).unwrap();
+        if value {
+            bits[(index / 8) as usize] |= 0x80 >> (index % 8);
+        } else {
+            bits[(index / 8) as usize] &= !(0x80 >> (index % 8));
+        }
+        self.status_lists.insert(&key, &bits);
+    }
+    //clear the bits of suspensions whose `resume_at` is past but nobody called `cert_resume` yet,
+    //so the published list lifts them at the same time as `cert_status`
+    pub(crate) fn internal_clear_lifted_suspensions(
+        &self,
+        category_id: &CategoryId,
+        bits: &mut [u8],
+    ) {
+        let now_ms = env::block_timestamp_ms();
+        let tokens = match self.suspended_per_category.get(category_id) {
+            Some(tokens) => tokens,
+            None => return,
+        };
+        for token_id in tokens.iter() {
+            let index = match self.status_index_by_token.get(&token_id) {
+                Some(index) => index,
+                None => continue,
+            };
+            let mask = 0x80 >> (index % 8);
+            if bits[(index / 8) as usize] & mask != 0
+                && self
+                    .suspensions_by_token
+                    .get(&token_id)
+                    .map(|suspension| suspension.is_lifted_at(now_ms))
+                    .unwrap_or(false)
+            {
+                bits[(index / 8) as usize] &= !mask;
+            }
+        }
+    }
+    //status of a certificate at `now_ms`
+    pub(crate) fn internal_cert_status(
+        &self,
+        token_id: &TokenId,
+        metadata: &TokenMetadata,
+        now_ms: u64,
+    ) -> CertStatus {
+        if self.internal_status_get(token_id, StatusPurpose::Revocation) {
+            CertStatus::Revoked
+        } else if self.internal_status_get(token_id, StatusPurpose::Suspension)
+            && !self
+                .suspensions_by_token
+                .get(token_id)
+                .map(|suspension| suspension.is_lifted_at(now_ms))
+                .unwrap_or(false)
+        {
+            CertStatus::Suspended
+        } else {
+            CertStatus::of(metadata, now_ms)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{context_at, setup};
+    use flate2::read::GzDecoder;
+    use near_sdk::testing_env;
+    use std::io::Read;
+
+    //inflate with a real gzip decoder, which also checks the CRC-32 and size trailer
+    fn gunzip(bytes: &[u8]) -> Vec<u8> {
+        let mut data = vec![];
+        GzDecoder::new(bytes).read_to_end(&mut data).unwrap();
+        data
+    }
+
+    //first bit of the published list, the one of certificate "0"
+    fn first_bit(contract: &Contract, purpose: StatusPurpose) -> bool {
+        let list = contract
+            .category_status_list("1".to_string(), purpose)
+            .unwrap();
+        let bytes = base64::decode_config(
+            &list.credential_subject.encoded_list,
+            base64::URL_SAFE_NO_PAD,
+        )
+        .unwrap();
+        gunzip(&bytes)[0] & 0x80 != 0
+    }
+
+    #[test]
+    fn status_list_lifts_lapsed_suspension() {
+        let mut contract = setup();
+        contract.cert_suspend("0".to_string(), None, Some(2_000));
+        assert!(first_bit(&contract, StatusPurpose::Suspension));
+        assert!(!first_bit(&contract, StatusPurpose::Revocation));
+
+        //nobody called `cert_resume`, the stored bit is still set
+        testing_env!(context_at(3, 0, 2_000).build());
+        assert!(contract.internal_status_get(&"0".to_string(), StatusPurpose::Suspension));
+        assert!(!first_bit(&contract, StatusPurpose::Suspension));
+        assert_eq!(
+            contract.cert_status("0".to_string()),
+            Some(CertStatus::Active)
+        );
+    }
+
+    #[test]
+    fn status_list_keeps_open_ended_suspension() {
+        let mut contract = setup();
+        contract.cert_suspend("0".to_string(), None, None);
+        testing_env!(context_at(3, 0, u64::MAX / 1_000_000).build());
+        assert!(first_bit(&contract, StatusPurpose::Suspension));
+    }
+
+    #[test]
+    fn suspended_tokens_of_category_follow_suspend_resume_and_delete() {
+        let mut contract = setup();
+        let suspended = |contract: &Contract| {
+            contract
+                .suspended_per_category
+                .get(&"1".to_string())
+                .map(|tokens| tokens.to_vec())
+                .unwrap_or_default()
+        };
+        contract.cert_suspend("0".to_string(), None, None);
+        assert_eq!(suspended(&contract), vec!["0".to_string()]);
+        testing_env!(context_at(1, 1, 1_000).build());
+        contract.cert_resume("0".to_string());
+        assert!(suspended(&contract).is_empty());
+
+        testing_env!(context_at(1, 10u128.pow(24), 1_000).build());
+        contract.cert_suspend("0".to_string(), None, None);
+        testing_env!(context_at(1, 1, 1_000).build());
+        contract.cert_delete("0".to_string());
+        assert!(suspended(&contract).is_empty());
+        assert!(contract.cert_suspension("0".to_string()).is_none());
+    }
+
+    #[test]
+    fn crc32_known_vectors() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"a"), 0xE8B7_BE43);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414F_A339
+        );
+    }
+
+    #[test]
+    fn gzip_empty_data() {
+        //header, an empty final fixed huffman block, then a zero CRC-32 and size
+        assert_eq!(
+            gzip(&[]),
+            vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff, 0x03, 0x00, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert!(gunzip(&gzip(&[])).is_empty());
+    }
+
+    #[test]
+    fn gzip_all_zeros() {
+        let data = vec![0u8; (STATUS_LIST_MIN_BITS / 8) as usize];
+        let bytes = gzip(&data);
+        assert_eq!(gunzip(&bytes), data);
+        //a default status list fits in a few hundred bytes
+        assert!(bytes.len() < 200);
+    }
+
+    #[test]
+    fn gzip_sparse_bits() {
+        let mut data = vec![0u8; (STATUS_LIST_MIN_BITS / 8) as usize];
+        //the first certificate is the left-most bit
+        for index in [0usize, 7, 8, 1_000, 1_001, 1_002, 65_535, 131_071] {
+            data[index / 8] |= 1 << (7 - index % 8);
+        }
+        assert_eq!(gunzip(&gzip(&data)), data);
+    }
+
+    #[test]
+    fn gzip_runs_longer_than_258() {
+        //runs split into copies of at most 258 bytes, with 0 to 2 bytes left as literals
+        for run in [257usize, 258, 259, 260, 261, 262, 516, 517, 1_000] {
+            let mut data = vec![0x5a];
+            data.extend(std::iter::repeat(0xa5).take(run));
+            data.push(0x5a);
+            assert_eq!(gunzip(&gzip(&data)), data, "run of {}", run);
+        }
+    }
+
+    #[test]
+    fn gzip_mixed_bytes() {
+        //every literal symbol range of the fixed huffman code, with and without runs
+        let data: Vec<u8> = (0..=255u8).chain((0..=255u8).rev()).chain([0; 3]).collect();
+        assert_eq!(gunzip(&gzip(&data)), data);
+    }
+}
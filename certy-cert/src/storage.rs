@@ -0,0 +1,58 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //Deposit NEAR that mints, updates and category creations of the account draw storage from
+    #[payable]
+    pub fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        self.internal_storage_deposit(account_id, registration_only)
+    }
+    #[payable]
+    pub fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+        self.internal_storage_withdraw(amount)
+    }
+    //Records already paid for stay in place, only the remaining balance is returned.
+    //Forcing would mean deleting the records of the account, which is not supported
+    #[payable]
+    pub fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+        self.internal_storage_unregister(force)
+    }
+    pub fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.internal_storage_balance_of(&account_id)
+    }
+    pub fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        storage_balance_bounds()
+    }
+}
+
+impl StorageManager for Contract {
+    fn storage_deposits(&self) -> &LookupMap<AccountId, Balance> {
+        &self.storage_deposits
+    }
+    fn storage_deposits_mut(&mut self) -> &mut LookupMap<AccountId, Balance> {
+        &mut self.storage_deposits
+    }
+    fn sponsorships_per_beneficiary(
+        &self,
+    ) -> &LookupMap<AccountId, UnorderedMap<AccountId, Balance>> {
+        &self.sponsorships_per_beneficiary
+    }
+    fn sponsorships_per_beneficiary_mut(
+        &mut self,
+    ) -> &mut LookupMap<AccountId, UnorderedMap<AccountId, Balance>> {
+        &mut self.sponsorships_per_beneficiary
+    }
+    fn sponsorships_prefix(beneficiary_id: &AccountId) -> Vec<u8> {
+        StorageKey::SponsorshipsPerBeneficiaryInner {
+            account_id_hash: hash_account_id(beneficiary_id),
+        }
+        .try_to_vec()
+        .unwrap()
+    }
+}
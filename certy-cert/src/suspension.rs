@@ -0,0 +1,160 @@
+use crate::*;
+
+//Temporary hold on a certificate, lifted by `cert_resume` or once `resume_at` is past
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Suspension {
+    pub suspended_by: AccountId, // cert provider who suspended the certificate and paid for this record
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>, // ex. "Pending investigation"
+    pub suspended_at: u64,       // When the certificate was suspended, Unix epoch in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resume_at: Option<u64>, // When the suspension lifts by itself, Unix epoch in milliseconds
+}
+
+impl Suspension {
+    pub(crate) fn is_lifted_at(&self, now_ms: u64) -> bool {
+        self.resume_at
+            .map(|resume_at| resume_at <= now_ms)
+            .unwrap_or(false)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    //Cert provider suspends a certificate, suspending it again replaces the reason and resume time
+    #[payable]
+    pub fn cert_suspend(
+        &mut self,
+        token_id: TokenId,
+        reason: Option<String>,
+        resume_at: Option<u64>,
+    ) {
+        assert_at_least_one_yocto();
+        self.assert_not_paused();
+        self.assert_cert_provider(env::predecessor_account_id(), &token_id);
+        self.assert_token_not_frozen(&token_id);
+        assert!(
+            !self.internal_status_get(&token_id, StatusPurpose::Revocation),
+            "Cert is revoked"
+        );
+        if let Some(resume_at) = resume_at {
+            assert!(
+                resume_at > env::block_timestamp_ms(),
+                "resume_at must be in the future"
+            );
+        }
+        let initial_storage_usage = env::storage_usage();
+        self.internal_suspension_add(
+            &token_id,
+            Suspension {
+                suspended_by: env::predecessor_account_id(),
+                reason: reason.clone(),
+                suspended_at: env::block_timestamp_ms(),
+                resume_at,
+            },
+        );
+        self.internal_status_set(&token_id, StatusPurpose::Suspension, true);
+        let cert_suspend_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::CertSuspend(vec![CertSuspendLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                token_ids: vec![token_id.to_string()],
+                reason,
+                resume_at,
+            }]),
+        };
+        cert_suspend_log.emit();
+        self.internal_storage_settle(&env::predecessor_account_id(), initial_storage_usage);
+    }
+    //Cert provider lifts a suspension, anyone can once its `resume_at` is past
+    //so the status list catches up. The freed storage goes back to the suspender
+    #[payable]
+    pub fn cert_resume(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        self.assert_not_paused();
+        let suspension = self
+            .suspensions_by_token
+            .get(&token_id)
+            .expect("Cert not suspended");
+        if !suspension.is_lifted_at(env::block_timestamp_ms()) {
+            self.assert_cert_provider(env::predecessor_account_id(), &token_id);
+        }
+        let category_id = self
+            .tokens_by_id
+            .get(&token_id)
+            .expect("No token")
+            .category_id;
+        let initial_storage_usage = env::storage_usage();
+        self.internal_suspension_remove(&category_id, &token_id);
+        self.internal_status_set(&token_id, StatusPurpose::Suspension, false);
+        let cert_resume_log: EventLog = EventLog {
+            standard: CERTY_CERT_STANDARD_NAME.to_string(),
+            version: CERTY_CERT_VERSION.to_string(),
+            event: EventLogVariant::CertResume(vec![CertResumeLog {
+                authorized_id: Some(env::predecessor_account_id().to_string()),
+                token_ids: vec![token_id.to_string()],
+            }]),
+        };
+        cert_resume_log.emit();
+        self.internal_storage_release(
+            &suspension.suspended_by,
+            &env::predecessor_account_id(),
+            initial_storage_usage,
+        );
+    }
+    pub fn cert_suspension(&self, token_id: TokenId) -> Option<Suspension> {
+        self.suspensions_by_token.get(&token_id)
+    }
+}
+
+impl Contract {
+    //record the suspension of a token and add it to the suspended tokens of its category
+    pub(crate) fn internal_suspension_add(&mut self, token_id: &TokenId, suspension: Suspension) {
+        let category_id = self
+            .tokens_by_id
+            .get(token_id)
+            .expect("No token")
+            .category_id;
+        self.suspensions_by_token.insert(token_id, &suspension);
+        //get the set of suspended tokens for the given category
+        let mut suspended_set = self
+            .suspended_per_category
+            .get(&category_id)
+            .unwrap_or_else(|| {
+                //if the category doesn't have any suspended tokens, we create a new unordered set
+                UnorderedSet::new(
+                    StorageKey::SuspendedPerCategoryInner {
+                        //we get a new unique prefix for the collection
+                        category_id_hash: hash_category_id(&category_id),
+                    }
+                    .try_to_vec()
+                    .unwrap(),
+                )
+            });
+        suspended_set.insert(token_id);
+        self.suspended_per_category
+            .insert(&category_id, &suspended_set);
+    }
+    //remove the suspension of a token and the token from the suspended tokens of its category
+    pub(crate) fn internal_suspension_remove(
+        &mut self,
+        category_id: &CategoryId,
+        token_id: &TokenId,
+    ) {
+        self.suspensions_by_token.remove(token_id);
+        let mut suspended_set = match self.suspended_per_category.get(category_id) {
+            Some(suspended_set) => suspended_set,
+            None => return,
+        };
+        suspended_set.remove(token_id);
+        //if the set is now empty, we remove the category from the suspended_per_category collection
+        if suspended_set.is_empty() {
+            self.suspended_per_category.remove(category_id);
+        } else {
+            self.suspended_per_category
+                .insert(category_id, &suspended_set);
+        }
+    }
+}
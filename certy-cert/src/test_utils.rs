@@ -0,0 +1,62 @@
+use crate::*;
+use near_sdk::test_utils::{accounts, VMContextBuilder};
+use near_sdk::testing_env;
+
+//call of accounts(predecessor) to the contract deployed at accounts(0)
+pub(crate) fn context(predecessor: usize, deposit: Balance) -> VMContextBuilder {
+    let mut builder = VMContextBuilder::new();
+    builder
+        .current_account_id(accounts(0))
+        .predecessor_account_id(accounts(predecessor))
+        .attached_deposit(deposit);
+    builder
+}
+
+//same as `context` at `now_ms`, Unix epoch in milliseconds
+pub(crate) fn context_at(predecessor: usize, deposit: Balance, now_ms: u64) -> VMContextBuilder {
+    let mut builder = context(predecessor, deposit);
+    builder.block_timestamp(now_ms * 1_000_000);
+    builder
+}
+
+pub(crate) fn category_metadata() -> CategoryMetadata {
+    CategoryMetadata {
+        title: Some("Certy".to_string()),
+        description: None,
+        media: None,
+        media_hash: None,
+        issued_at: None,
+        updated_at: None,
+        fields: None,
+        extra: None,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+pub(crate) fn token_metadata() -> TokenMetadata {
+    TokenMetadata {
+        title: Some("Certy".to_string()),
+        description: None,
+        media: None,
+        media_hash: None,
+        copies: None,
+        issued_at: None,
+        expires_at: None,
+        starts_at: None,
+        updated_at: None,
+        extra: None,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+//category "1" of accounts(1) with certificate "0" minted to accounts(2).
+//Leaves accounts(1) calling with 1 NEAR attached
+pub(crate) fn setup() -> Contract {
+    testing_env!(context(1, 10u128.pow(24)).build());
+    let mut contract = Contract::new_default_meta(accounts(0));
+    contract.category_create("1".to_string(), category_metadata());
+    contract.nft_mint(token_metadata(), accounts(2), "1".to_string());
+    contract
+}
@@ -0,0 +1,269 @@
+use crate::*;
+use near_sdk::Gas;
+
+//gas kept by `upgrade` for deploying the code, the rest is given to `migrate`
+const GAS_FOR_UPGRADE: Gas = Gas(20_000_000_000_000);
+//least gas `migrate` is given to read and rewrite the state
+const MIN_GAS_FOR_MIGRATE: Gas = Gas(10_000_000_000_000);
+
+//Layout of the contract state, stored first in `Contract`. A variant is added for every released
+//layout of `Contract`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StateVersion {
+    V1,
+}
+
+//The state layout deployed before the state was versioned
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV0 {
+    pub owner_id: AccountId,
+    pub count_token_id: u128,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub categories_per_owner: LookupMap<AccountId, UnorderedSet<CategoryId>>,
+    pub tokens_per_category: LookupMap<CategoryId, UnorderedSet<TokenId>>,
+    pub tokens_by_id: LookupMap<TokenId, Token>,
+    pub categories_by_id: LookupMap<CategoryId, Category>,
+    pub token_metadata_by_id: UnorderedMap<TokenId, TokenMetadata>,
+    pub category_metadata_by_id: UnorderedMap<CategoryId, CategoryMetadata>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+}
+
+impl From<ContractV0> for Contract {
+    fn from(old: ContractV0) -> Self {
+        Self {
+            state_version: StateVersion::V1,
+            owner_id: old.owner_id,
+            pending_owner_id: None,
+            admins: UnorderedSet::new(StorageKey::Admins.try_to_vec().unwrap()),
+            paused: false,
+            frozen_categories: UnorderedSet::new(
+                StorageKey::FrozenCategories.try_to_vec().unwrap(),
+            ),
+            count_token_id: old.count_token_id,
+            tokens_per_owner: old.tokens_per_owner,
+            categories_per_owner: old.categories_per_owner,
+            tokens_per_category: old.tokens_per_category,
+            tokens_by_id: old.tokens_by_id,
+            categories_by_id: old.categories_by_id,
+            token_metadata_by_id: old.token_metadata_by_id,
+            category_metadata_by_id: old.category_metadata_by_id,
+            metadata: old.metadata,
+            cert_changes_per_holder: LookupMap::new(
+                StorageKey::CertChangesPerHolder.try_to_vec().unwrap(),
+            ),
+            endorsements_per_token: LookupMap::new(
+                StorageKey::EndorsementsPerToken.try_to_vec().unwrap(),
+            ),
+            verifiers: UnorderedSet::new(StorageKey::Verifiers.try_to_vec().unwrap()),
+            verified_issuers: UnorderedMap::new(StorageKey::VerifiedIssuers.try_to_vec().unwrap()),
+            root_accreditors: UnorderedSet::new(StorageKey::RootAccreditors.try_to_vec().unwrap()),
+            accreditations: LookupMap::new(StorageKey::Accreditations.try_to_vec().unwrap()),
+            accredited_per_accreditor: LookupMap::new(
+                StorageKey::AccreditedPerAccreditor.try_to_vec().unwrap(),
+            ),
+            storage_deposits: LookupMap::new(StorageKey::StorageDeposits.try_to_vec().unwrap()),
+            sponsorships_per_beneficiary: LookupMap::new(
+                StorageKey::SponsorshipsPerBeneficiary.try_to_vec().unwrap(),
+            ),
+            storage_payer_by_token: LookupMap::new(
+                StorageKey::StoragePayerByToken.try_to_vec().unwrap(),
+            ),
+            storage_payer_by_category: LookupMap::new(
+                StorageKey::StoragePayerByCategory.try_to_vec().unwrap(),
+            ),
+            fee_config: FeeConfig::default(),
+            fees_collected: 0,
+            category_prices: LookupMap::new(StorageKey::CategoryPrices.try_to_vec().unwrap()),
+            ft_whitelist: UnorderedSet::new(StorageKey::FtWhitelist.try_to_vec().unwrap()),
+            tokens_per_owner_category: LookupMap::new(
+                StorageKey::TokensPerOwnerCategory.try_to_vec().unwrap(),
+            ),
+            certs_by_issued_at: TreeMap::new(StorageKey::CertsByIssuedAt.try_to_vec().unwrap()),
+            certs_by_issued_at_per_category: LookupMap::new(
+                StorageKey::CertsByIssuedAtPerCategory.try_to_vec().unwrap(),
+            ),
+            tokens_per_document_hash: LookupMap::new(
+                StorageKey::TokensPerDocumentHash.try_to_vec().unwrap(),
+            ),
+            status_index_by_token: LookupMap::new(
+                StorageKey::StatusIndexByToken.try_to_vec().unwrap(),
+            ),
+            status_list_size_per_category: LookupMap::new(
+                StorageKey::StatusListSizePerCategory.try_to_vec().unwrap(),
+            ),
+            status_lists: LookupMap::new(StorageKey::StatusLists.try_to_vec().unwrap()),
+            suspensions_by_token: LookupMap::new(
+                StorageKey::SuspensionsByToken.try_to_vec().unwrap(),
+            ),
+            suspended_per_category: LookupMap::new(
+                StorageKey::SuspendedPerCategory.try_to_vec().unwrap(),
+            ),
+            appeals_per_token: LookupMap::new(StorageKey::AppealsPerToken.try_to_vec().unwrap()),
+            arbiters_per_category: LookupMap::new(
+                StorageKey::ArbitersPerCategory.try_to_vec().unwrap(),
+            ),
+            did_records: LookupMap::new(StorageKey::DidRecords.try_to_vec().unwrap()),
+            issuer_keys: LookupMap::new(StorageKey::IssuerKeys.try_to_vec().unwrap()),
+            ft_payouts: LookupMap::new(StorageKey::FtPayouts.try_to_vec().unwrap()),
+            credential_base_url: None,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    //Called by `upgrade` right after the new code is deployed, moves the stored state to the current layout
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let state = env::storage_read(b"STATE").expect("Contract is not initialized");
+        //the versioned layout starts with its marker, followed by the u32 length of `owner_id`
+        //whose low byte is never 0. The baseline layout starts with that length, so its second byte is 0
+        if state.len() > 1 && state[1] != 0 {
+            match StateVersion::try_from_slice(&state[..1]).expect("Unknown state layout") {
+                StateVersion::V1 => Contract::try_from_slice(&state).expect("Unknown state layout"),
+            }
+        } else {
+            Contract::from(ContractV0::try_from_slice(&state).expect("Unknown state layout"))
+        }
+    }
+    //Contract owner deploys the code passed as raw input and migrates the state
+    pub fn upgrade(&self) -> Promise {
+        self.assert_owner();
+        let gas_for_migrate = env::prepaid_gas()
+            .0
+            .saturating_sub(env::used_gas().0)
+            .saturating_sub(GAS_FOR_UPGRADE.0);
+        assert!(
+            gas_for_migrate >= MIN_GAS_FOR_MIGRATE.0,
+            "Not enough gas attached for the upgrade"
+        );
+        let code = env::input().expect("No code attached");
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call("migrate".to_string(), vec![], 0, Gas(gas_for_migrate))
+    }
+    //Contract owner adds the tokens minted before an index existed to the token indexes,
+    //in batches of `limit` tokens. Returns the `from_index` of the next batch, none when done
+    #[payable]
+    pub fn migrate_token_indexes(
+        &mut self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Option<U128> {
+        assert_at_least_one_yocto();
+        self.assert_owner();
+        let initial_storage_usage = env::storage_usage();
+        let page = paginate(
+            self.token_metadata_by_id.keys_as_vector(),
+            from_index,
+            limit,
+        );
+        for token_id in page.items.iter() {
+            self.internal_token_index(token_id);
+        }
+        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
+        refund_deposit(required_storage_in_bytes);
+        page.next_cursor
+    }
+    pub fn state_version(&self) -> &StateVersion {
+        &self.state_version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    //writes the state the baseline contract left after creating category "category" as
+    //accounts(1) and minting certificate "0" to accounts(2)
+    fn write_v0_state() {
+        let entries: Vec<(Vec<u8>, Vec<u8>)> =
+            BorshDeserialize::try_from_slice(include_bytes!("../tests/fixtures/v0_state.borsh"))
+                .unwrap();
+        for (key, value) in entries.iter() {
+            env::storage_write(key, value);
+        }
+    }
+
+    #[test]
+    fn migrate_from_v0() {
+        testing_env!(VMContextBuilder::new().build());
+        write_v0_state();
+
+        let contract = Contract::migrate();
+
+        assert_eq!(contract.state_version, StateVersion::V1);
+        assert_eq!(contract.owner_id, accounts(0));
+        assert_eq!(contract.count_token_id, 1);
+        assert!(contract.pending_owner_id.is_none());
+        assert!(!contract.paused);
+        let category = contract.category_info("category".to_string()).unwrap();
+        assert_eq!(category.owner_id, accounts(1));
+        assert!(!category.verified);
+        let token = contract.nft_token("0".to_string()).unwrap();
+        assert_eq!(token.owner_id, accounts(2));
+        assert_eq!(token.metadata.title, Some("Certy".to_string()));
+        assert_eq!(contract.nft_metadata().name, "Certy NFT");
+    }
+
+    #[test]
+    fn migrate_token_indexes_from_v0() {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(10u128.pow(24))
+            .build());
+        write_v0_state();
+        let mut contract = Contract::migrate();
+        assert!(!contract.holds_cert(accounts(2), "category".to_string(), None));
+
+        let next_from_index = contract.migrate_token_indexes(None, Some(10));
+
+        assert!(next_from_index.is_none());
+        assert!(contract.holds_cert(accounts(2), "category".to_string(), None));
+        let certs =
+            contract.certs_for_owner_in_category(accounts(2), "category".to_string(), None, None);
+        assert_eq!(certs.len(), 1);
+        assert_eq!(certs[0].token_id, "0");
+        let feed = contract.certs_issued_latest(Some("category".to_string()), None, None);
+        assert_eq!(feed.items.len(), 1);
+        assert_eq!(feed.items[0].token_id, "0");
+    }
+
+    #[test]
+    fn migrate_current_state_is_noop() {
+        testing_env!(VMContextBuilder::new().build());
+        let mut contract = Contract::new_default_meta(accounts(0));
+        contract.paused = true;
+        env::state_write(&contract);
+
+        let migrated = Contract::migrate();
+
+        assert_eq!(migrated.state_version, StateVersion::V1);
+        assert!(migrated.paused);
+        assert_eq!(migrated.nft_metadata().name, "Certy NFT");
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown state layout")]
+    fn migrate_unknown_state_panics() {
+        testing_env!(VMContextBuilder::new().build());
+        env::storage_write(b"STATE", &[1, 2, 3]);
+        Contract::migrate();
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough gas attached for the upgrade")]
+    fn upgrade_without_gas_for_migrate_panics() {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .prepaid_gas(GAS_FOR_UPGRADE)
+            .build());
+        let contract = Contract::new_default_meta(accounts(0));
+        contract.upgrade();
+    }
+}
@@ -0,0 +1,172 @@
+use crate::*;
+use near_sdk::ext_contract;
+
+//Result of a holder check, kept small so it is cheap to pass back to a callback
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CertHolderCheck {
+    //whether the account holds a valid certificate of every category checked
+    pub valid: bool,
+    //a valid certificate of each category, in the order the categories were given
+    pub token_ids: Vec<Option<TokenId>>,
+}
+
+//Interface other contracts call to verify certificates, copy it next to the callback that reads
+//the result. `CertHolderCheck` is returned as JSON
+#[ext_contract(ext_certy_cert)]
+pub trait CertyCertVerifier {
+    fn cert_check_holder(
+        &self,
+        account_id: AccountId,
+        category_ids: Vec<CategoryId>,
+        valid_at: Option<u64>,
+    ) -> CertHolderCheck;
+    fn holds_cert(
+        &self,
+        account_id: AccountId,
+        category_id: CategoryId,
+        active_only: Option<bool>,
+    ) -> bool;
+}
+
+#[near_bindgen]
+impl Contract {
+    //Check that an account holds a certificate of each category that is valid at `valid_at`,
+    //Unix epoch in milliseconds, now if not given. Checking no category is never valid
+    pub fn cert_check_holder(
+        &self,
+        account_id: AccountId,
+        category_ids: Vec<CategoryId>,
+        valid_at: Option<u64>,
+    ) -> CertHolderCheck {
+        let valid_at = valid_at.unwrap_or_else(env::block_timestamp_ms);
+        let token_ids: Vec<Option<TokenId>> = category_ids
+            .into_iter()
+            .map(|category_id| {
+                self.internal_valid_cert_of(account_id.clone(), category_id, valid_at)
+            })
+            .collect();
+        CertHolderCheck {
+            valid: !token_ids.is_empty() && token_ids.iter().all(|token_id| token_id.is_some()),
+            token_ids,
+        }
+    }
+}
+
+impl Contract {
+    //a certificate of the category held by the account that is valid at `valid_at`
+    pub(crate) fn internal_valid_cert_of(
+        &self,
+        account_id: AccountId,
+        category_id: CategoryId,
+        valid_at: u64,
+    ) -> Option<TokenId> {
+        let tokens_set = self
+            .tokens_per_owner_category
+            .get(&(account_id, category_id))?;
+        let valid_token_id = tokens_set.iter().find(|token_id| {
+            let metadata = self.token_metadata_by_id.get(token_id).unwrap();
+            self.internal_cert_status(token_id, &metadata, valid_at) == CertStatus::Active
+        });
+        valid_token_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{context, setup};
+    use near_sdk::mock::VmAction;
+    use near_sdk::test_utils::{accounts, get_created_receipts};
+    use near_sdk::{serde_json, testing_env, Gas, PromiseResult, RuntimeFeesConfig, VMConfig};
+
+    //what the callback of a calling contract does with the result of `cert_check_holder`
+    fn on_cert_checked() -> bool {
+        match env::promise_result(0) {
+            PromiseResult::Successful(value) => serde_json::from_slice::<CertHolderCheck>(&value)
+                .map(|check| check.valid)
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn cert_check_holder_without_categories_is_not_valid() {
+        let contract = setup();
+        let check = contract.cert_check_holder(accounts(2), vec![], None);
+        assert!(!check.valid);
+        assert!(check.token_ids.is_empty());
+    }
+
+    #[test]
+    fn cert_check_holder_needs_every_category() {
+        let contract = setup();
+        let check = contract.cert_check_holder(accounts(2), vec!["1".to_string()], None);
+        assert!(check.valid);
+        assert_eq!(check.token_ids, vec![Some("0".to_string())]);
+        let check =
+            contract.cert_check_holder(accounts(2), vec!["1".to_string(), "2".to_string()], None);
+        assert!(!check.valid);
+        assert_eq!(check.token_ids, vec![Some("0".to_string()), None]);
+    }
+
+    #[test]
+    fn ext_certy_cert_calls_cert_check_holder() {
+        testing_env!(context(4, 0).current_account_id(accounts(4)).build());
+        ext_certy_cert::cert_check_holder(
+            accounts(2),
+            vec!["1".to_string()],
+            None,
+            accounts(0),
+            0,
+            Gas(5_000_000_000_000),
+        );
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(0));
+        match &receipts[0].actions[0] {
+            VmAction::FunctionCall {
+                function_name,
+                args,
+                ..
+            } => {
+                assert_eq!(function_name, "cert_check_holder");
+                let args: serde_json::Value = serde_json::from_slice(args).unwrap();
+                assert_eq!(args["account_id"], accounts(2).to_string());
+                assert_eq!(args["category_ids"], serde_json::json!(["1"]));
+            }
+            _ => panic!("Expected a function call"),
+        }
+    }
+
+    #[test]
+    fn callback_reads_cert_check_holder_result() {
+        let contract = setup();
+        let result = |category_ids: Vec<CategoryId>| {
+            serde_json::to_vec(&contract.cert_check_holder(accounts(2), category_ids, None))
+                .unwrap()
+        };
+        for (category_ids, expected) in [
+            (vec!["1".to_string()], true),
+            (vec!["2".to_string()], false),
+            (vec![], false),
+        ] {
+            testing_env!(
+                context(0, 0).current_account_id(accounts(4)).build(),
+                VMConfig::test(),
+                RuntimeFeesConfig::test(),
+                Default::default(),
+                vec![PromiseResult::Successful(result(category_ids))],
+            );
+            assert_eq!(on_cert_checked(), expected);
+        }
+        testing_env!(
+            context(0, 0).current_account_id(accounts(4)).build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed],
+        );
+        assert!(!on_cert_checked());
+    }
+}
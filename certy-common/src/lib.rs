@@ -0,0 +1,6 @@
+//Code shared by the Certy contracts
+pub use crate::pagination::*;
+pub use crate::storage::*;
+
+mod pagination;
+mod storage;
@@ -0,0 +1,79 @@
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{UnorderedMap, Vector};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+
+//A page of a paginated view, `next_cursor` is passed back to get the next page, none on the last page.
+//Views paginated by index return the `from_index` of the next page: a position in the collection,
+//not an entry, so pages read while the collection changes can overlap or leave entries out
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Page<T, C = U128> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<C>,
+}
+
+impl<T, C> Page<T, C> {
+    pub fn empty() -> Self {
+        Page {
+            items: vec![],
+            next_cursor: None,
+        }
+    }
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Page<U, C> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+//indexes of the page from `from_index` up to `limit` in a collection of `len` elements
+fn page_range(len: u64, from_index: Option<U128>, limit: Option<u64>) -> std::ops::Range<u64> {
+    let start = std::cmp::min(u128::from(from_index.unwrap_or(U128(0))), u128::from(len)) as u64;
+    let end = std::cmp::min(start.saturating_add(limit.unwrap_or(50)), len);
+    start..end
+}
+
+//the `from_index` of the page after the one ending at `end`
+fn next_from_index(len: u64, end: u64) -> Option<U128> {
+    if end < len {
+        Some(U128(u128::from(end)))
+    } else {
+        None
+    }
+}
+
+//read the elements of a vector from `from_index` up to `limit` by index,
+//so the gas used only depends on the page size and not on the position in the vector
+pub fn paginate<T: BorshDeserialize + BorshSerialize>(
+    elements: &Vector<T>,
+    from_index: Option<U128>,
+    limit: Option<u64>,
+) -> Page<T> {
+    let range = page_range(elements.len(), from_index, limit);
+    Page {
+        next_cursor: next_from_index(elements.len(), range.end),
+        items: range.filter_map(|index| elements.get(index)).collect(),
+    }
+}
+
+//same as `paginate` for the entries of a map, keys and values are stored at the same index
+pub fn paginate_map<K, V>(
+    map: &UnorderedMap<K, V>,
+    from_index: Option<U128>,
+    limit: Option<u64>,
+) -> Page<(K, V)>
+where
+    K: BorshDeserialize + BorshSerialize,
+    V: BorshDeserialize + BorshSerialize,
+{
+    let (keys, values) = (map.keys_as_vector(), map.values_as_vector());
+    let range = page_range(keys.len(), from_index, limit);
+    Page {
+        next_cursor: next_from_index(keys.len(), range.end),
+        items: range
+            .filter_map(|index| Some((keys.get(index)?, values.get(index)?)))
+            .collect(),
+    }
+}
@@ -0,0 +1,390 @@
+use near_sdk::collections::{LookupMap, UnorderedMap};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, AccountId, Balance, Promise};
+
+//bytes taken by an entry of `storage_deposits`: 40 bytes of record overhead, 1 byte of prefix,
+//the longest borsh serialized account ID (4 + 64 bytes) and the balance (16 bytes)
+pub const STORAGE_BALANCE_ENTRY_BYTES: u64 = 40 + 1 + 4 + 64 + 16;
+
+//bytes taken by an entry of the storage payer collections, with a record ID of up to 64 bytes
+pub const STORAGE_PAYER_ENTRY_BYTES: u64 = 40 + 1 + 4 + 64 + 4 + 64;
+
+//NEP-145 storage balance of an account
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    pub total: U128,
+    pub available: U128,
+}
+
+//NEP-145 storage balance bounds
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalanceBounds {
+    pub min: U128,
+    pub max: Option<U128>,
+}
+
+//The Json sponsorship is what will be returned from view calls.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonSponsorship {
+    pub sponsor_id: AccountId,
+    pub beneficiary_id: AccountId,
+    pub available: U128,
+}
+
+//NEP-145 storage bounds: registering costs the storage of the balance entry
+pub fn storage_balance_bounds() -> StorageBalanceBounds {
+    StorageBalanceBounds {
+        min: U128(Balance::from(STORAGE_BALANCE_ENTRY_BYTES) * env::storage_byte_cost()),
+        max: None,
+    }
+}
+
+//Storage balances and sponsorships of a contract: who pays for the records an account creates
+//and who gets the storage back when they are removed
+pub trait StorageManager {
+    //NEP-145 storage balance available for a given account
+    fn storage_deposits(&self) -> &LookupMap<AccountId, Balance>;
+    fn storage_deposits_mut(&mut self) -> &mut LookupMap<AccountId, Balance>;
+    //storage sponsorship left by sponsor for a given beneficiary
+    fn sponsorships_per_beneficiary(
+        &self,
+    ) -> &LookupMap<AccountId, UnorderedMap<AccountId, Balance>>;
+    fn sponsorships_per_beneficiary_mut(
+        &mut self,
+    ) -> &mut LookupMap<AccountId, UnorderedMap<AccountId, Balance>>;
+    //unique prefix of the sponsorships of a beneficiary
+    fn sponsorships_prefix(beneficiary_id: &AccountId) -> Vec<u8>;
+
+    //NEP-145 `storage_deposit`, the deposit goes to `account_id` or the caller
+    fn internal_storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let registration_only = registration_only.unwrap_or(false);
+        let min_balance = storage_balance_bounds().min.0;
+        if let Some(available) = self.storage_deposits().get(&account_id) {
+            if registration_only {
+                if amount > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(amount);
+                }
+            } else {
+                self.storage_deposits_mut()
+                    .insert(&account_id, &(available + amount));
+            }
+        } else {
+            assert!(
+                amount >= min_balance,
+                "Must attach at least {} yoctoNEAR to register",
+                min_balance
+            );
+            if registration_only {
+                self.storage_deposits_mut().insert(&account_id, &0);
+                let refund = amount - min_balance;
+                if refund > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(refund);
+                }
+            } else {
+                self.storage_deposits_mut()
+                    .insert(&account_id, &(amount - min_balance));
+            }
+        }
+        self.internal_storage_balance_of(&account_id).unwrap()
+    }
+    //NEP-145 `storage_withdraw` of the caller, all of the available balance if no amount is given
+    fn internal_storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        let account_id = env::predecessor_account_id();
+        let available = self
+            .storage_deposits()
+            .get(&account_id)
+            .expect("Account is not registered");
+        let amount = amount.map(|amount| amount.0).unwrap_or(available);
+        assert!(
+            amount <= available,
+            "Can't withdraw more than the available storage balance"
+        );
+        self.storage_deposits_mut()
+            .insert(&account_id, &(available - amount));
+        if amount > 0 {
+            Promise::new(account_id.clone()).transfer(amount);
+        }
+        self.internal_storage_balance_of(&account_id).unwrap()
+    }
+    //NEP-145 `storage_unregister` of the caller. Records already paid for stay in place,
+    //only the remaining balance is returned. Forcing would mean deleting the records of the account,
+    //which is not supported
+    fn internal_storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert!(
+            force != Some(true),
+            "Forced unregistration is not supported, records of the account are kept"
+        );
+        let account_id = env::predecessor_account_id();
+        if let Some(available) = self.storage_deposits_mut().remove(&account_id) {
+            Promise::new(account_id).transfer(available + storage_balance_bounds().min.0);
+            true
+        } else {
+            false
+        }
+    }
+    fn internal_storage_balance_of(&self, account_id: &AccountId) -> Option<StorageBalance> {
+        self.storage_deposits()
+            .get(account_id)
+            .map(|available| StorageBalance {
+                total: U128(available + storage_balance_bounds().min.0),
+                available: U128(available),
+            })
+    }
+    //settle the storage difference since `initial_storage_usage` with the account:
+    //used storage is paid by the attached deposit and then by the storage balance of the account,
+    //released storage is credited back to the storage balance or refunded if the account is not registered
+    fn internal_storage_settle(&mut self, account_id: &AccountId, initial_storage_usage: u64) {
+        self.internal_storage_settle_from(
+            account_id,
+            initial_storage_usage,
+            env::attached_deposit(),
+        )
+    }
+    //same as `internal_storage_settle` with the part of the attached deposit left for storage
+    fn internal_storage_settle_from(
+        &mut self,
+        account_id: &AccountId,
+        initial_storage_usage: u64,
+        attached_deposit: Balance,
+    ) {
+        let mut refund = attached_deposit;
+        if env::storage_usage() > initial_storage_usage {
+            let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
+            let required_cost = env::storage_byte_cost() * Balance::from(required_storage_in_bytes);
+            refund = self.internal_storage_pay(account_id, required_cost, attached_deposit);
+        } else if env::storage_usage() < initial_storage_usage {
+            let released_storage = initial_storage_usage - env::storage_usage();
+            let released_cost = Balance::from(released_storage) * env::storage_byte_cost();
+            if let Some(available) = self.storage_deposits().get(account_id) {
+                self.storage_deposits_mut()
+                    .insert(account_id, &(available + released_cost));
+            } else {
+                refund += released_cost;
+            }
+        }
+
+        //if the refund is greater than 1 yocto NEAR, we refund the account that amount
+        if refund > 1 {
+            Promise::new(account_id.clone()).transfer(refund);
+        }
+    }
+    //pay `required_cost` for the account from the attached deposit and then from its storage balance,
+    //returns the part of the attached deposit left
+    fn internal_storage_pay(
+        &mut self,
+        account_id: &AccountId,
+        required_cost: Balance,
+        attached_deposit: Balance,
+    ) -> Balance {
+        if required_cost <= attached_deposit {
+            return attached_deposit - required_cost;
+        }
+        let missing_cost = required_cost - attached_deposit;
+        let available = self.storage_deposits().get(account_id).unwrap_or(0);
+        assert!(
+            missing_cost <= available,
+            "Must attach {} yoctoNEAR or deposit it with storage_deposit to cover storage",
+            missing_cost,
+        );
+        self.storage_deposits_mut()
+            .insert(account_id, &(available - missing_cost));
+        0
+    }
+    //pick the account paying the storage of the records an account creates:
+    //the first sponsor of the account with enough sponsorship left, the account itself otherwise
+    fn internal_storage_payer(
+        &self,
+        account_id: &AccountId,
+        required_storage_in_bytes: u64,
+    ) -> AccountId {
+        let required_cost = env::storage_byte_cost() * Balance::from(required_storage_in_bytes);
+        self.sponsorships_per_beneficiary()
+            .get(account_id)
+            .and_then(|sponsorships| {
+                sponsorships
+                    .iter()
+                    .find(|(_, available)| *available >= required_cost)
+                    .map(|(sponsor_id, _)| sponsor_id)
+            })
+            .unwrap_or_else(|| account_id.clone())
+    }
+    //charge the storage used since `initial_storage_usage` by the account to the payer,
+    //the account pays the part the sponsorship of the payer doesn't cover
+    fn internal_storage_charge(
+        &mut self,
+        payer_id: &AccountId,
+        account_id: &AccountId,
+        initial_storage_usage: u64,
+        attached_deposit: Balance,
+    ) {
+        if payer_id == account_id {
+            return self.internal_storage_settle_from(
+                account_id,
+                initial_storage_usage,
+                attached_deposit,
+            );
+        }
+        let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
+        let required_cost = env::storage_byte_cost() * Balance::from(required_storage_in_bytes);
+        let available = self.internal_sponsorship_get(payer_id, account_id);
+        let sponsored_cost = std::cmp::min(required_cost, available);
+        self.internal_sponsorship_set(payer_id, account_id, available - sponsored_cost);
+        //what the sponsorship doesn't cover is paid by the account like any storage it uses
+        let refund =
+            self.internal_storage_pay(account_id, required_cost - sponsored_cost, attached_deposit);
+        //if the refund is greater than 1 yocto NEAR, we refund the account that amount
+        if refund > 1 {
+            Promise::new(account_id.clone()).transfer(refund);
+        }
+    }
+    //credit the storage released since `initial_storage_usage` by the account back to the payer
+    fn internal_storage_release(
+        &mut self,
+        payer_id: &AccountId,
+        account_id: &AccountId,
+        initial_storage_usage: u64,
+    ) {
+        self.internal_storage_release_from(
+            payer_id,
+            account_id,
+            initial_storage_usage,
+            env::attached_deposit(),
+        )
+    }
+    //same as `internal_storage_release` with the part of the attached deposit to give back to the account
+    fn internal_storage_release_from(
+        &mut self,
+        payer_id: &AccountId,
+        account_id: &AccountId,
+        initial_storage_usage: u64,
+        attached_deposit: Balance,
+    ) {
+        if payer_id == account_id || env::storage_usage() >= initial_storage_usage {
+            return self.internal_storage_settle_from(
+                account_id,
+                initial_storage_usage,
+                attached_deposit,
+            );
+        }
+        let released_storage = initial_storage_usage - env::storage_usage();
+        let released_cost = Balance::from(released_storage) * env::storage_byte_cost();
+        let available = self.internal_sponsorship_get(payer_id, account_id);
+        if available > 0 {
+            //the sponsorship is still running, the released storage goes back into it
+            self.internal_sponsorship_set(payer_id, account_id, available + released_cost);
+        } else {
+            Promise::new(payer_id.clone()).transfer(released_cost);
+        }
+        if attached_deposit > 1 {
+            Promise::new(account_id.clone()).transfer(attached_deposit);
+        }
+    }
+    //settle the storage a record paid by `payer_id` grew or shrank by since `initial_storage_usage`,
+    //when the account updates it
+    fn internal_storage_adjust(
+        &mut self,
+        payer_id: &AccountId,
+        account_id: &AccountId,
+        initial_storage_usage: u64,
+    ) {
+        if env::storage_usage() > initial_storage_usage {
+            self.internal_storage_charge(
+                payer_id,
+                account_id,
+                initial_storage_usage,
+                env::attached_deposit(),
+            )
+        } else {
+            self.internal_storage_release(payer_id, account_id, initial_storage_usage)
+        }
+    }
+    //add the attached deposit to the sponsorship of the caller for a beneficiary,
+    //returns the amount added once the sponsorship record is paid for and the sponsorship left
+    fn internal_sponsor_deposit(&mut self, beneficiary_id: &AccountId) -> (Balance, Balance) {
+        let sponsor_id = env::predecessor_account_id();
+        let amount = env::attached_deposit();
+        let initial_storage_usage = env::storage_usage();
+        let available = self.internal_sponsorship_get(&sponsor_id, beneficiary_id);
+        self.internal_sponsorship_set(&sponsor_id, beneficiary_id, available + amount);
+        //the sponsorship record itself is paid from the deposit
+        let required_cost =
+            Balance::from(env::storage_usage() - initial_storage_usage) * env::storage_byte_cost();
+        assert!(
+            required_cost < amount,
+            "Must attach more than {} yoctoNEAR to cover storage",
+            required_cost,
+        );
+        let available = available + amount - required_cost;
+        self.internal_sponsorship_set(&sponsor_id, beneficiary_id, available);
+        (amount - required_cost, available)
+    }
+    //take back the unused sponsorship of the caller, all of it if no amount is given.
+    //Returns the amount taken back and the sponsorship left
+    fn internal_sponsor_withdraw(
+        &mut self,
+        beneficiary_id: &AccountId,
+        amount: Option<U128>,
+    ) -> (Balance, Balance) {
+        let sponsor_id = env::predecessor_account_id();
+        let available = self.internal_sponsorship_get(&sponsor_id, beneficiary_id);
+        let amount = amount.map(|amount| amount.0).unwrap_or(available);
+        assert!(
+            amount <= available,
+            "Can't withdraw more than the available sponsorship"
+        );
+        let initial_storage_usage = env::storage_usage();
+        self.internal_sponsorship_set(&sponsor_id, beneficiary_id, available - amount);
+        let released_cost =
+            Balance::from(initial_storage_usage - env::storage_usage()) * env::storage_byte_cost();
+        if amount + released_cost > 0 {
+            Promise::new(sponsor_id).transfer(amount + released_cost);
+        }
+        (amount, available - amount)
+    }
+    //get the sponsorship left from a sponsor to a beneficiary
+    fn internal_sponsorship_get(
+        &self,
+        sponsor_id: &AccountId,
+        beneficiary_id: &AccountId,
+    ) -> Balance {
+        self.sponsorships_per_beneficiary()
+            .get(beneficiary_id)
+            .and_then(|sponsorships| sponsorships.get(sponsor_id))
+            .unwrap_or(0)
+    }
+    //set the sponsorship left from a sponsor to a beneficiary, removing it when nothing is left
+    fn internal_sponsorship_set(
+        &mut self,
+        sponsor_id: &AccountId,
+        beneficiary_id: &AccountId,
+        available: Balance,
+    ) {
+        let mut sponsorships = self
+            .sponsorships_per_beneficiary()
+            .get(beneficiary_id)
+            .unwrap_or_else(|| UnorderedMap::new(Self::sponsorships_prefix(beneficiary_id)));
+        if available > 0 {
+            sponsorships.insert(sponsor_id, &available);
+        } else {
+            sponsorships.remove(sponsor_id);
+        }
+
+        if sponsorships.is_empty() {
+            self.sponsorships_per_beneficiary_mut()
+                .remove(beneficiary_id);
+        } else {
+            self.sponsorships_per_beneficiary_mut()
+                .insert(beneficiary_id, &sponsorships);
+        }
+    }
+}